@@ -0,0 +1,94 @@
+use crate::diagnostics::diagnostic::{has_errors, Diagnostic};
+use crate::diagnostics::render::render_all;
+use crate::parser::parser::parse_source;
+use crate::validator::validator::validate;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+pub fn new_check_command() -> Command {
+    Command::new("check")
+        .about("Check DSL scripts for errors without generating manifests")
+        .arg(
+            Arg::new("dsl_files")
+                .help("Paths to the DSL scripts")
+                .required(true)
+                .action(ArgAction::Append)
+                .index(1),
+        )
+        .arg_required_else_help(true)
+}
+
+pub fn execute_check_command(matches: &ArgMatches) {
+    let mut failed = 0;
+    for path in matches.get_many::<String>("dsl_files").unwrap() {
+        // Read the DSL script from the file
+        let input = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("error: could not read {}: {}", path, err);
+                failed += 1;
+                continue;
+            }
+        };
+
+        let diagnostics = check_source(&input);
+        if !diagnostics.is_empty() {
+            eprintln!("{}", render_all(&diagnostics, &input, path));
+        }
+        if has_errors(&diagnostics) {
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        eprintln!("error: {} file(s) failed the check", failed);
+        std::process::exit(1);
+    }
+}
+
+// check_source returns every problem found in a script: the lexer or parser
+// errors when it does not parse, otherwise the result of semantic validation.
+pub fn check_source(input: &str) -> Vec<Diagnostic> {
+    match parse_source(input) {
+        Ok(program) => validate(&program),
+        Err(diagnostics) => diagnostics,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::codes;
+
+    #[test]
+    fn test_new_check_command() {
+        let mut app = Command::new("test").subcommand(new_check_command());
+        let matches = app
+            .try_get_matches_from_mut(vec!["test", "check", "a.kp", "b.kp"])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("check").unwrap();
+        let files: Vec<&str> = sub_matches
+            .get_many::<String>("dsl_files")
+            .unwrap()
+            .map(|s| s.as_str())
+            .collect();
+        assert_eq!(files, vec!["a.kp", "b.kp"]);
+    }
+
+    #[test]
+    fn test_check_command_requires_a_file() {
+        let mut app = Command::new("test").subcommand(new_check_command());
+        assert!(app.try_get_matches_from_mut(vec!["test", "check"]).is_err());
+    }
+
+    #[test]
+    fn test_check_source() {
+        assert!(check_source(include_str!("../../examples/web_service.kp")).is_empty());
+
+        let diagnostics = check_source("deploy app web { replicas: -2; }");
+        let codes: Vec<&str> = diagnostics.iter().map(|d| d.code).collect();
+        assert_eq!(codes, vec![codes::MISSING_IMAGE, codes::OUT_OF_RANGE]);
+
+        let diagnostics = check_source("deploy app web { replicas: 2 }}");
+        assert_eq!(diagnostics[0].code, codes::UNEXPECTED_TOKEN);
+    }
+}
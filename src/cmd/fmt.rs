@@ -0,0 +1,92 @@
+use crate::diagnostics::render::render_all;
+use crate::formatter::formatter::format_source;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+pub fn new_fmt_command() -> Command {
+    Command::new("fmt")
+        .about("Format DSL scripts in canonical form")
+        .arg(
+            Arg::new("dsl_files")
+                .help("Paths to the DSL scripts")
+                .required(true)
+                .action(ArgAction::Append)
+                .index(1),
+        )
+        .arg(
+            Arg::new("check")
+                .help("Report files that are not formatted instead of rewriting them")
+                .long("check")
+                .action(ArgAction::SetTrue),
+        )
+        .arg_required_else_help(true)
+}
+
+pub fn execute_fmt_command(matches: &ArgMatches) {
+    let check = matches.get_flag("check");
+    let mut failed = false;
+
+    for path in matches.get_many::<String>("dsl_files").unwrap() {
+        // Read the DSL script from the file
+        let input = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("error: could not read {}: {}", path, err);
+                failed = true;
+                continue;
+            }
+        };
+
+        let formatted = match format_source(&input) {
+            Ok(formatted) => formatted,
+            Err(diagnostics) => {
+                eprintln!("{}", render_all(&diagnostics, &input, path));
+                failed = true;
+                continue;
+            }
+        };
+        if formatted == input {
+            continue;
+        }
+
+        // In check mode list the file, otherwise rewrite it in place
+        if check {
+            println!("{}", path);
+            failed = true;
+        } else if let Err(err) = std::fs::write(path, formatted) {
+            eprintln!("error: could not write {}: {}", path, err);
+            failed = true;
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_fmt_command() {
+        let mut app = Command::new("test").subcommand(new_fmt_command());
+        let matches = app
+            .try_get_matches_from_mut(vec!["test", "fmt", "a.kp", "b.kp"])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("fmt").unwrap();
+        assert_eq!(
+            sub_matches.get_many::<String>("dsl_files").unwrap().len(),
+            2
+        );
+        assert!(!sub_matches.get_flag("check"));
+    }
+
+    #[test]
+    fn test_fmt_command_check_flag() {
+        let mut app = Command::new("test").subcommand(new_fmt_command());
+        let matches = app
+            .try_get_matches_from_mut(vec!["test", "fmt", "--check", "a.kp"])
+            .unwrap();
+        assert!(matches.subcommand_matches("fmt").unwrap().get_flag("check"));
+    }
+}
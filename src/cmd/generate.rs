@@ -1,4 +1,8 @@
-use crate::lexer::{self, lexer::Lexer};
+use crate::diagnostics::diagnostic::has_errors;
+use crate::diagnostics::render::render_all;
+use crate::generator::generator::generate;
+use crate::parser::parser::parse_source;
+use crate::validator::validator::validate;
 use clap::{Arg, ArgMatches, Command};
 
 pub fn new_generate_command() -> Command {
@@ -25,24 +29,44 @@ pub fn execute_generate_command(matches: &ArgMatches) {
     let output_file = matches.get_one::<String>("output_file");
 
     // Read the DSL script from the file
-    let raw_input = match std::fs::read_to_string(dsl_file_path) {
+    let input = match std::fs::read_to_string(dsl_file_path) {
         Ok(content) => content,
         Err(err) => {
-            eprintln!("Error reading DSL file: {}", err);
+            eprintln!("error: could not read {}: {}", dsl_file_path, err);
             std::process::exit(1);
         }
     };
 
-    // Trim the input DSL script
-    let input = raw_input.trim();
+    // Lex and parse the script into nodes
+    let program = match parse_source(&input) {
+        Ok(program) => program,
+        Err(diagnostics) => {
+            eprint!("{}", render_all(&diagnostics, &input, dsl_file_path));
+            std::process::exit(1);
+        }
+    };
+
+    // Report semantic problems; warnings do not stop generation
+    let diagnostics = validate(&program);
+    if !diagnostics.is_empty() {
+        eprint!("{}", render_all(&diagnostics, &input, dsl_file_path));
+    }
+    if has_errors(&diagnostics) {
+        std::process::exit(1);
+    }
 
-    // Initialize the lexer (assuming you have a lexer module)
-    let lexer = Lexer::new(input);
+    let manifest = generate(&program);
 
-    println!("Generate command executed with DSL file: {}", dsl_file_path);
-    // Pretty-print the content of the lexer
-    println!("Lexer content: {:#?}", lexer);
-    print!("Output file: {:#?}", output_file);
+    // Write the manifest to the output file, or to stdout when none is given
+    match output_file {
+        Some(path) => {
+            if let Err(err) = std::fs::write(path, manifest) {
+                eprintln!("error: could not write {}: {}", path, err);
+                std::process::exit(1);
+            }
+        }
+        None => print!("{}", manifest),
+    }
 }
 
 #[cfg(test)]
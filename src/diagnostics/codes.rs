@@ -0,0 +1,93 @@
+// Contains the diagnostic codes reported by the lexer, parser and validator.
+// K00xx codes come from the lexer, K01xx from the parser, K02xx from the validator.
+
+// An unexpected character in the source.
+pub const UNEXPECTED_CHARACTER: &str = "K0001";
+// A string literal that is not closed.
+pub const UNTERMINATED_STRING: &str = "K0002";
+// A block comment that is not closed.
+pub const UNTERMINATED_COMMENT: &str = "K0003";
+// An escape sequence that a double-quoted string does not support.
+pub const INVALID_ESCAPE: &str = "K0004";
+
+// A token that does not fit the grammar at its position.
+pub const UNEXPECTED_TOKEN: &str = "K0101";
+// A block that reaches the end of input before its closing brace.
+pub const UNCLOSED_BLOCK: &str = "K0102";
+// A value that should be a number but is not.
+pub const INVALID_NUMBER: &str = "K0103";
+// A value outside the set accepted by a field.
+pub const INVALID_VALUE: &str = "K0104";
+// A required field that is not set.
+pub const MISSING_FIELD: &str = "K0105";
+// A key that appears twice in the same block.
+pub const DUPLICATE_KEY: &str = "K0106";
+// A `${name}` reference to a variable that no `let` defines.
+pub const UNDEFINED_VARIABLE: &str = "K0107";
+// A variable defined by more than one `let`.
+pub const DUPLICATE_VARIABLE: &str = "K0108";
+// Variables whose values refer to each other in a loop.
+pub const VARIABLE_CYCLE: &str = "K0109";
+
+// A deployment without a container image.
+pub const MISSING_IMAGE: &str = "K0201";
+// Two resources of the same kind share a name within a namespace.
+pub const DUPLICATE_NAME: &str = "K0202";
+// A name or key that Kubernetes does not accept.
+pub const INVALID_NAME: &str = "K0203";
+// A number outside the range allowed for its field.
+pub const OUT_OF_RANGE: &str = "K0204";
+// A resource request larger than the matching limit.
+pub const REQUEST_EXCEEDS_LIMIT: &str = "K0205";
+// A reference to a named port that the deployment does not declare.
+pub const UNKNOWN_PORT: &str = "K0206";
+// A reference to a node that the script does not define.
+pub const UNKNOWN_REFERENCE: &str = "K0207";
+// An autoscaling utilization target for a resource the containers do not request.
+pub const MISSING_REQUEST: &str = "K0208";
+// A disruption budget that never allows a pod to be evicted.
+pub const BLOCKED_EVICTION: &str = "K0209";
+// A role rule for a resource or verb that the built-in API table does not list.
+pub const UNKNOWN_RESOURCE: &str = "K0210";
+// A port name of a workload, or a port number of a service, written twice.
+pub const DUPLICATE_PORT: &str = "K0211";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_are_unique() {
+        let mut codes = vec![
+            UNEXPECTED_CHARACTER,
+            UNTERMINATED_STRING,
+            UNTERMINATED_COMMENT,
+            INVALID_ESCAPE,
+            UNEXPECTED_TOKEN,
+            UNCLOSED_BLOCK,
+            INVALID_NUMBER,
+            INVALID_VALUE,
+            MISSING_FIELD,
+            DUPLICATE_KEY,
+            UNDEFINED_VARIABLE,
+            DUPLICATE_VARIABLE,
+            VARIABLE_CYCLE,
+            MISSING_IMAGE,
+            DUPLICATE_NAME,
+            INVALID_NAME,
+            OUT_OF_RANGE,
+            REQUEST_EXCEEDS_LIMIT,
+            UNKNOWN_PORT,
+            UNKNOWN_REFERENCE,
+            MISSING_REQUEST,
+            BLOCKED_EVICTION,
+            UNKNOWN_RESOURCE,
+            DUPLICATE_PORT,
+        ];
+        let count = codes.len();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), count);
+        assert!(codes.iter().all(|c| c.len() == 5 && c.starts_with('K')));
+    }
+}
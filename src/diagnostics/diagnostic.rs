@@ -0,0 +1,118 @@
+use crate::lexer::lexer::Span;
+use std::fmt;
+
+// Severity tells whether a diagnostic stops the command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+// Label points at a secondary location that explains a diagnostic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+// Diagnostic is a problem found in a `.kp` file, located by its primary span.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub span: Span,
+    pub labels: Vec<Label>,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn error(code: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            code,
+            message: message.into(),
+            span,
+            labels: Vec::new(),
+            help: None,
+        }
+    }
+
+    pub fn warning(code: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(code, message, span)
+        }
+    }
+
+    // with_label adds a secondary location to the diagnostic.
+    pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    // with_help attaches a hint on how to fix the problem.
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+// has_errors reports whether any diagnostic is an error.
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(Diagnostic::is_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_builder() {
+        let diagnostic = Diagnostic::error("K0101", "expected `:`", Span::new(4, 5))
+            .with_label(Span::new(0, 3), "in this field")
+            .with_help("write `key: value`");
+
+        assert!(diagnostic.is_error());
+        assert_eq!(diagnostic.labels.len(), 1);
+        assert_eq!(diagnostic.labels[0].message, "in this field");
+        assert_eq!(diagnostic.help.as_deref(), Some("write `key: value`"));
+        assert_eq!(diagnostic.to_string(), "error[K0101]: expected `:`");
+    }
+
+    #[test]
+    fn test_warning() {
+        let diagnostic = Diagnostic::warning("K0201", "careful", Span::default());
+        assert!(!diagnostic.is_error());
+        assert_eq!(diagnostic.to_string(), "warning[K0201]: careful");
+        assert!(!has_errors(std::slice::from_ref(&diagnostic)));
+        assert!(has_errors(&[
+            diagnostic,
+            Diagnostic::error("K0101", "bad", Span::default())
+        ]));
+    }
+}
@@ -0,0 +1,180 @@
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::lexer::lexer::Span;
+
+// line_col returns the 1-based line and character column of a byte offset.
+pub fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+// Annotation is one underlined span of a rendered diagnostic.
+struct Annotation<'a> {
+    line: usize,
+    column: usize,
+    width: usize,
+    marker: char,
+    message: &'a str,
+}
+
+// render formats a diagnostic in the style of rustc: the message, the location
+// and every referenced source line with the spans underlined.
+//
+//     error[K0101]: expected `:`, found `3`
+//      --> app.kp:2:14
+//       |
+//     2 |     replicas 3;
+//       |              ^
+pub fn render(diagnostic: &Diagnostic, source: &str, file_name: &str) -> String {
+    let lines: Vec<&str> = source.split('\n').collect();
+
+    let mut annotations = vec![annotate(source, diagnostic.span, '^', "")];
+    for label in &diagnostic.labels {
+        annotations.push(annotate(source, label.span, '-', &label.message));
+    }
+    annotations.sort_by_key(|a| a.line);
+
+    let gutter = annotations
+        .iter()
+        .map(|a| a.line.to_string().len())
+        .max()
+        .unwrap_or(1);
+    let pad = " ".repeat(gutter);
+    let (line, column) = line_col(source, diagnostic.span.start);
+
+    let mut out = format!("{}\n", diagnostic);
+    out.push_str(&format!("{}--> {}:{}:{}\n", pad, file_name, line, column));
+    out.push_str(&format!("{} |\n", pad));
+
+    let mut last_line = 0;
+    for annotation in &annotations {
+        if annotation.line != last_line {
+            let text = lines
+                .get(annotation.line - 1)
+                .copied()
+                .unwrap_or("")
+                .trim_end_matches('\r');
+            let row = format!("{:>width$} | {}", annotation.line, text, width = gutter);
+            out.push_str(row.trim_end());
+            out.push('\n');
+            last_line = annotation.line;
+        }
+        let underline = format!(
+            "{}{}",
+            " ".repeat(annotation.column - 1),
+            annotation.marker.to_string().repeat(annotation.width)
+        );
+        let row = if annotation.message.is_empty() {
+            underline
+        } else {
+            format!("{} {}", underline, annotation.message)
+        };
+        out.push_str(&format!("{} | {}\n", pad, row));
+    }
+
+    if let Some(help) = &diagnostic.help {
+        out.push_str(&format!("{} |\n", pad));
+        out.push_str(&format!("{} = help: {}\n", pad, help));
+    }
+    out
+}
+
+// render_all renders every diagnostic, separated by blank lines.
+pub fn render_all(diagnostics: &[Diagnostic], source: &str, file_name: &str) -> String {
+    diagnostics
+        .iter()
+        .map(|d| render(d, source, file_name))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// annotate locates a span. Spans that run past the end of their first line are
+// underlined up to the end of that line; empty spans get a single marker.
+fn annotate<'a>(source: &str, span: Span, marker: char, message: &'a str) -> Annotation<'a> {
+    let start = span.start.min(source.len());
+    let end = span.end.clamp(start, source.len());
+    let (line, column) = line_col(source, start);
+    let line_end = source[start..]
+        .find('\n')
+        .map(|i| start + i)
+        .unwrap_or(source.len());
+    let width = source[start..end.min(line_end)].chars().count().max(1);
+    Annotation {
+        line,
+        column,
+        width,
+        marker,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_col() {
+        let source = "ab\ncdé\nf";
+        assert_eq!(line_col(source, 0), (1, 1));
+        assert_eq!(line_col(source, 3), (2, 1));
+        assert_eq!(line_col(source, 8), (3, 1));
+        assert_eq!(line_col(source, 7), (2, 4));
+        assert_eq!(line_col(source, 100), (3, 2));
+    }
+
+    #[test]
+    fn test_render_primary_span() {
+        let source = "deploy app a {\n    replicas 3;\n}";
+        let diagnostic = Diagnostic::error("K0101", "expected `:`, found `3`", Span::new(28, 29))
+            .with_help("fields are written as `key: value;`");
+        let expected = "\
+error[K0101]: expected `:`, found `3`
+ --> app.kp:2:14
+  |
+2 |     replicas 3;
+  |              ^
+  |
+  = help: fields are written as `key: value;`
+";
+        assert_eq!(render(&diagnostic, source, "app.kp"), expected);
+    }
+
+    #[test]
+    fn test_render_secondary_label() {
+        let source = "deploy app a {\n    replicas: 3;";
+        let diagnostic = Diagnostic::error("K0102", "block is not closed", Span::new(32, 32))
+            .with_label(Span::new(13, 14), "block opened here");
+        let expected = "\
+error[K0102]: block is not closed
+ --> app.kp:2:17
+  |
+1 | deploy app a {
+  |              - block opened here
+2 |     replicas: 3;
+  |                 ^
+";
+        assert_eq!(render(&diagnostic, source, "app.kp"), expected);
+    }
+
+    #[test]
+    fn test_render_multi_line_span() {
+        let source = "image: \"abc\nnext";
+        let diagnostic = Diagnostic::error("K0002", "unterminated string", Span::new(7, 16));
+        let rendered = render(&diagnostic, source, "x.kp");
+        assert!(rendered.contains("1 | image: \"abc\n  |        ^^^^\n"));
+    }
+
+    #[test]
+    fn test_render_all() {
+        let source = "a\nb";
+        let diagnostics = vec![
+            Diagnostic::error("K0001", "first", Span::new(0, 1)),
+            Diagnostic::warning("K0001", "second", Span::new(2, 3)),
+        ];
+        let rendered = render_all(&diagnostics, source, "x.kp");
+        assert!(rendered.contains("error[K0001]: first\n --> x.kp:1:1\n"));
+        assert!(rendered.contains("\n\nwarning[K0001]: second\n --> x.kp:2:1\n"));
+    }
+}
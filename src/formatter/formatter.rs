@@ -0,0 +1,969 @@
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::lexer::lexer::{Lexer, LexerInterface, Token, Trivia};
+use crate::lexer::token::TokenType;
+use crate::parser::parser::parse_source;
+
+const INDENT: &str = "    ";
+
+// Canonical order of the fields of a workload block: a deployment,
+// statefulset, job or cronjob. Fields not listed keep their relative order
+// after the listed ones.
+const DEPLOYMENT_FIELD_ORDER: [TokenType; 27] = [
+    TokenType::TokenNamespace,
+    TokenType::TokenLabels,
+    TokenType::TokenAnnotations,
+    TokenType::TokenReplicas,
+    TokenType::TokenServiceName,
+    TokenType::TokenPodManagementPolicy,
+    TokenType::TokenUpdateStrategy,
+    TokenType::TokenSchedule,
+    TokenType::TokenConcurrencyPolicy,
+    TokenType::TokenCompletions,
+    TokenType::TokenParallelism,
+    TokenType::TokenBackoffLimit,
+    TokenType::TokenActiveDeadlineSeconds,
+    TokenType::TokenRestartPolicy,
+    TokenType::TokenServiceAccount,
+    TokenType::TokenImage,
+    TokenType::TokenCommand,
+    TokenType::TokenArgs,
+    TokenType::TokenPorts,
+    TokenType::TokenEnv,
+    TokenType::TokenEnvFrom,
+    TokenType::TokenResources,
+    TokenType::TokenAutoscale,
+    TokenType::TokenDisruption,
+    TokenType::TokenNetwork,
+    TokenType::TokenProbes,
+    TokenType::TokenStorage,
+];
+
+// Canonical order of the fields of the other blocks. Like workloads, they
+// start with `namespace`, `labels` and `annotations`.
+const SERVICE_FIELD_ORDER: [TokenType; 6] = [
+    TokenType::TokenNamespace,
+    TokenType::TokenLabels,
+    TokenType::TokenAnnotations,
+    TokenType::TokenTypeString,
+    TokenType::TokenApp,
+    TokenType::TokenPorts,
+];
+
+const INGRESS_FIELD_ORDER: [TokenType; 6] = [
+    TokenType::TokenNamespace,
+    TokenType::TokenLabels,
+    TokenType::TokenAnnotations,
+    TokenType::TokenHost,
+    TokenType::TokenTls,
+    TokenType::TokenRoutes,
+];
+
+const CONFIG_FIELD_ORDER: [TokenType; 4] = [
+    TokenType::TokenNamespace,
+    TokenType::TokenLabels,
+    TokenType::TokenAnnotations,
+    TokenType::TokenData,
+];
+
+const SECRET_FIELD_ORDER: [TokenType; 5] = [
+    TokenType::TokenNamespace,
+    TokenType::TokenLabels,
+    TokenType::TokenAnnotations,
+    TokenType::TokenTypeString,
+    TokenType::TokenData,
+];
+
+const ROLE_FIELD_ORDER: [TokenType; 5] = [
+    TokenType::TokenNamespace,
+    TokenType::TokenLabels,
+    TokenType::TokenAnnotations,
+    TokenType::TokenScope,
+    TokenType::TokenRules,
+];
+
+// Comments holds the comments of the tokens that make up one printed line:
+// leading comments go on their own lines above it, trailing ones at its end.
+#[derive(Debug, Default, PartialEq)]
+struct Comments {
+    leading: Vec<Trivia>,
+    trailing: Vec<Trivia>,
+}
+
+// Item is a node of the token tree the formatter prints. Unlike the parser's
+// nodes it keeps every token as written, comments included, so the script can
+// be printed back without losing information.
+#[derive(Debug, PartialEq)]
+enum Item {
+    // `key: value;`; a list or reference value keeps its brackets and commas
+    Field {
+        key: Token,
+        value: Vec<Token>,
+        comments: Comments,
+    },
+    // `word ... ;` such as `allow from app(web) on http;`
+    Statement {
+        tokens: Vec<Token>,
+        comments: Comments,
+    },
+    // `header { items }`; `closing` holds the comments of the `}`
+    Block {
+        header: Vec<Token>,
+        items: Vec<Item>,
+        comments: Comments,
+        closing: Comments,
+    },
+    // `---`
+    Separator {
+        comments: Comments,
+    },
+}
+
+// format_source re-prints a script in canonical form: one field per line with
+// a terminating `;`, blocks indented by four spaces, deployment fields in a
+// fixed order and blank lines between blocks. Comments move with the field or
+// block they are attached to. Scripts that do not parse are returned as
+// diagnostics and left alone.
+pub fn format_source(input: &str) -> Result<String, Vec<Diagnostic>> {
+    parse_source(input)?;
+
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    let eof = loop {
+        let token = lexer.next_token();
+        if token.token_type == TokenType::TokenEOF {
+            break token;
+        }
+        tokens.push(token);
+    };
+
+    let items = TreeBuilder { tokens, pos: 0 }.items(&mut Comments::default());
+    Ok(print(&items, &eof.leading_trivia, input))
+}
+
+// TreeBuilder groups the tokens of a script that is known to parse into items.
+struct TreeBuilder {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl TreeBuilder {
+    // items reads items up to the `}` closing the current block, or to the end
+    // of input at the top level. The comments of the `}` go to `closing`.
+    fn items(&mut self, closing: &mut Comments) -> Vec<Item> {
+        let mut items = Vec::new();
+        while let Some(token) = self.peek(0) {
+            let mut comments = Comments::default();
+            match token.token_type {
+                TokenType::TokenRBrace => {
+                    self.next(closing);
+                    break;
+                }
+                TokenType::TokenSeparator => {
+                    self.next(&mut comments);
+                    items.push(Item::Separator { comments });
+                }
+                TokenType::TokenSemicolon => {
+                    self.next(&mut comments);
+                }
+                _ if self.peek_type(1) == Some(&TokenType::TokenColon) => {
+                    let key = self.next(&mut comments);
+                    self.next(&mut comments);
+                    let mut value = vec![self.next(&mut comments)];
+                    let close = match (&value[0].token_type, self.peek_type(0)) {
+                        (TokenType::TokenLBracket, _) => Some(TokenType::TokenRBracket),
+                        (_, Some(TokenType::TokenLParen)) => Some(TokenType::TokenRParen),
+                        _ => None,
+                    };
+                    if let Some(close) = close {
+                        while value[value.len() - 1].token_type != close {
+                            value.push(self.next(&mut comments));
+                        }
+                    }
+                    if self.peek_type(0) == Some(&TokenType::TokenSemicolon) {
+                        self.next(&mut comments);
+                    }
+                    items.push(Item::Field {
+                        key,
+                        value,
+                        comments,
+                    });
+                }
+                _ if self.statement_length().is_some() => {
+                    let length = self.statement_length().unwrap_or_default();
+                    let tokens = (0..length).map(|_| self.next(&mut comments)).collect();
+                    if self.peek_type(0) == Some(&TokenType::TokenSemicolon) {
+                        self.next(&mut comments);
+                    }
+                    items.push(Item::Statement { tokens, comments });
+                }
+                _ if !self.starts_block() => {
+                    let mut tokens = Vec::new();
+                    while self.peek_type(0).is_some_and(|t| {
+                        !matches!(t, TokenType::TokenSemicolon | TokenType::TokenRBrace)
+                    }) {
+                        tokens.push(self.next(&mut comments));
+                    }
+                    if self.peek_type(0) == Some(&TokenType::TokenSemicolon) {
+                        self.next(&mut comments);
+                    }
+                    items.push(Item::Statement { tokens, comments });
+                }
+                _ => {
+                    let mut header = Vec::new();
+                    while self
+                        .peek_type(0)
+                        .is_some_and(|t| *t != TokenType::TokenLBrace)
+                    {
+                        header.push(self.next(&mut comments));
+                    }
+                    self.next(&mut comments);
+                    let mut closing = Comments::default();
+                    let items_in_block = self.items(&mut closing);
+                    items.push(Item::Block {
+                        header,
+                        items: items_in_block,
+                        comments,
+                        closing,
+                    });
+                }
+            }
+        }
+        items
+    }
+
+    // statement_length returns the number of tokens of a statement whose `;` is
+    // optional: `let <name> = <value>`, `bind <role> to <account>` and
+    // `deny all <direction>`. Such a statement ends after its last token rather
+    // than at the next `;` or `}`.
+    fn statement_length(&self) -> Option<usize> {
+        match (self.peek_type(0)?, self.peek_type(1), self.peek_type(2)) {
+            (TokenType::TokenLet, _, Some(TokenType::TokenEquals)) => Some(4),
+            (TokenType::TokenBind, _, Some(TokenType::TokenTo)) => Some(4),
+            (TokenType::TokenDeny, Some(TokenType::TokenAll), _) => Some(3),
+            _ => None,
+        }
+    }
+
+    // starts_block reports whether the tokens ahead open a block, rather than
+    // end as a statement at a `;` or `}`.
+    fn starts_block(&self) -> bool {
+        self.tokens[self.pos..]
+            .iter()
+            .find(|t| {
+                matches!(
+                    t.token_type,
+                    TokenType::TokenLBrace | TokenType::TokenSemicolon | TokenType::TokenRBrace
+                )
+            })
+            .is_some_and(|t| t.token_type == TokenType::TokenLBrace)
+    }
+
+    fn peek(&self, ahead: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + ahead)
+    }
+
+    fn peek_type(&self, ahead: usize) -> Option<&TokenType> {
+        self.peek(ahead).map(|t| &t.token_type)
+    }
+
+    // next consumes a token, moving its comments to the line it is printed on.
+    fn next(&mut self, comments: &mut Comments) -> Token {
+        let mut token = self.tokens[self.pos].clone();
+        self.pos += 1;
+        comments.leading.append(&mut token.leading_trivia);
+        comments.trailing.append(&mut token.trailing_trivia);
+        token
+    }
+}
+
+// print renders the top-level items followed by the comments at the end of the
+// input. Blocks of a document are separated by a blank line and documents by a
+// `---` line. Consecutive statements such as `bind` stay together.
+fn print(items: &[Item], trailing_comments: &[Trivia], source: &str) -> String {
+    let mut out = String::new();
+    let mut first_in_document = true;
+    let mut previous: Option<&Item> = None;
+    for item in items {
+        if let Item::Separator { .. } = item {
+            write_item(&mut out, item, 0, source);
+            first_in_document = true;
+            continue;
+        }
+        let grouped = matches!(
+            (previous, item),
+            (Some(Item::Statement { .. }), Item::Statement { .. })
+        );
+        if !first_in_document && !grouped {
+            out.push('\n');
+        }
+        write_item(&mut out, item, 0, source);
+        first_in_document = false;
+        previous = Some(item);
+    }
+    if !trailing_comments.is_empty() && !first_in_document {
+        out.push('\n');
+    }
+    write_comments(&mut out, trailing_comments, "");
+    out
+}
+
+fn write_item(out: &mut String, item: &Item, depth: usize, source: &str) {
+    let indent = INDENT.repeat(depth);
+    match item {
+        Item::Field {
+            key,
+            value,
+            comments,
+        } => {
+            let line = format!(
+                "{}: {};",
+                token_text(key, source),
+                value_text(value, source)
+            );
+            write_line(out, &indent, &line, comments);
+        }
+        Item::Statement { tokens, comments } => {
+            let line = format!("{};", statement_text(tokens, source));
+            write_line(out, &indent, &line, comments);
+        }
+        Item::Block {
+            header,
+            items,
+            comments,
+            closing,
+        } => {
+            let header_text: Vec<&str> = header.iter().map(|t| token_text(t, source)).collect();
+            write_line(
+                out,
+                &indent,
+                &format!("{} {{", header_text.join(" ")),
+                comments,
+            );
+
+            let mut items: Vec<&Item> = items.iter().collect();
+            if let Some(order) = field_order(&header[0].token_type) {
+                items.sort_by_key(|item| field_rank(item, order));
+            }
+            for item in items {
+                write_item(out, item, depth + 1, source);
+            }
+            write_comments(out, &closing.leading, &INDENT.repeat(depth + 1));
+            write_line(
+                out,
+                &indent,
+                "}",
+                &Comments {
+                    leading: Vec::new(),
+                    trailing: closing.trailing.clone(),
+                },
+            );
+        }
+        Item::Separator { comments } => write_line(out, &indent, "---", comments),
+    }
+}
+
+// write_line writes the leading comments, then the line with its trailing comments.
+fn write_line(out: &mut String, indent: &str, line: &str, comments: &Comments) {
+    write_comments(out, &comments.leading, indent);
+    out.push_str(indent);
+    out.push_str(line);
+    for comment in &comments.trailing {
+        out.push(' ');
+        out.push_str(&comment.text);
+    }
+    out.push('\n');
+}
+
+// write_comments writes comments on lines of their own.
+fn write_comments(out: &mut String, comments: &[Trivia], indent: &str) {
+    for comment in comments {
+        out.push_str(indent);
+        out.push_str(&comment.text);
+        out.push('\n');
+    }
+}
+
+// field_order returns the canonical field order of a top-level block, or None
+// for a nested block whose fields keep their source order.
+fn field_order(block: &TokenType) -> Option<&'static [TokenType]> {
+    match block {
+        TokenType::TokenDeployApp
+        | TokenType::TokenDeployStatefulSet
+        | TokenType::TokenJob
+        | TokenType::TokenCronJob => Some(&DEPLOYMENT_FIELD_ORDER),
+        TokenType::TokenService => Some(&SERVICE_FIELD_ORDER),
+        TokenType::TokenIngress => Some(&INGRESS_FIELD_ORDER),
+        TokenType::TokenConfig => Some(&CONFIG_FIELD_ORDER),
+        TokenType::TokenSecret => Some(&SECRET_FIELD_ORDER),
+        TokenType::TokenRole => Some(&ROLE_FIELD_ORDER),
+        _ => None,
+    }
+}
+
+// field_rank returns the position of a field in the canonical order of its block.
+fn field_rank(item: &Item, order: &[TokenType]) -> usize {
+    let key = match item {
+        Item::Field { key, .. } => key,
+        Item::Statement { tokens, .. } => &tokens[0],
+        Item::Block { header, .. } => &header[0],
+        Item::Separator { .. } => return order.len(),
+    };
+    order
+        .iter()
+        .position(|t| *t == key.token_type)
+        .unwrap_or(order.len())
+}
+
+// value_text returns the text of a field value. Lists and references are
+// printed on one line as `[a, b]` and `secret(a, b)`, without a trailing comma.
+fn value_text(value: &[Token], source: &str) -> String {
+    let mut out = String::new();
+    for (i, token) in value.iter().enumerate() {
+        if token.token_type != TokenType::TokenComma {
+            out.push_str(token_text(token, source));
+        } else if !matches!(
+            value.get(i + 1).map(|t| &t.token_type),
+            Some(TokenType::TokenRBracket | TokenType::TokenRParen)
+        ) {
+            out.push_str(", ");
+        }
+    }
+    out
+}
+
+// statement_text returns the text of a statement: words separated by single
+// spaces, with references such as `app(web)` and lists such as `a, b` printed
+// the way value_text prints them.
+fn statement_text(tokens: &[Token], source: &str) -> String {
+    let mut out = String::new();
+    let mut space = false;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.token_type {
+            TokenType::TokenComma => {
+                if tokens.get(i + 1).map(|t| &t.token_type) != Some(&TokenType::TokenRParen) {
+                    out.push(',');
+                }
+            }
+            TokenType::TokenLParen => {
+                out.push('(');
+                space = false;
+                continue;
+            }
+            TokenType::TokenRParen => out.push(')'),
+            _ => {
+                if space {
+                    out.push(' ');
+                }
+                out.push_str(token_text(token, source));
+            }
+        }
+        space = true;
+    }
+    out
+}
+
+// token_text returns the text a token is printed as. Strings are copied from
+// the source so that their quoting and escapes are kept as written.
+fn token_text<'a>(token: &'a Token, source: &'a str) -> &'a str {
+    match token.token_type {
+        TokenType::TokenString => &source[token.span.start..token.span.end],
+        _ => &token.value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::codes;
+
+    fn format(input: &str) -> String {
+        format_source(input).unwrap()
+    }
+
+    #[test]
+    fn test_format_examples_are_canonical() {
+        for example in [
+            include_str!("../../examples/basic_app.kp"),
+            include_str!("../../examples/web_service.kp"),
+        ] {
+            assert_eq!(format(example), example);
+        }
+    }
+
+    #[test]
+    fn test_format_layout() {
+        let input = "deploy app web{\n  image:\"nginx\"\n\tports{http:80}replicas:2;}";
+        let expected = "\
+deploy app web {
+    replicas: 2;
+    image: \"nginx\";
+    ports {
+        http: 80;
+    }
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_deployment_field_order() {
+        let input = "\
+deploy app web {
+    storage { volume: data; size: 1Gi; mountPath: /data; }
+    probes { liveness { tcp: 80; } }
+    resources { requests { cpu: 100m; } }
+    env { MODE: prod; }
+    ports { http: 80; }
+    image: nginx;
+    annotations { note: x; }
+    replicas: 2;
+    labels { tier: web; }
+    namespace: shop;
+}";
+        let output = format(input);
+        let keys: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("    "))
+            .filter(|line| !line.starts_with([' ', '}']))
+            .filter_map(|line| line.split([':', ' ']).next())
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "namespace",
+                "labels",
+                "annotations",
+                "replicas",
+                "image",
+                "ports",
+                "env",
+                "resources",
+                "probes",
+                "storage"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_statefulset_field_order() {
+        let input = "\
+deploy  statefulset db {
+    storage { volume: data; size: 1Gi; mountPath: /data; }
+    image: postgres;
+    updateStrategy: OnDelete;
+    serviceName: db-headless;
+    replicas: 3;
+    podManagementPolicy: Parallel;
+}";
+        let expected = "\
+deploy statefulset db {
+    replicas: 3;
+    serviceName: db-headless;
+    podManagementPolicy: Parallel;
+    updateStrategy: OnDelete;
+    image: postgres;
+    storage {
+        volume: data;
+        size: 1Gi;
+        mountPath: /data;
+    }
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_cronjob_field_order() {
+        let input = "\
+cronjob report {
+    image: reporter;
+    restartPolicy: OnFailure;
+    backoffLimit: 2;
+    concurrencyPolicy: Forbid;
+    schedule: \"0 3 * * *\";
+    namespace: batch;
+}";
+        let expected = "\
+cronjob report {
+    namespace: batch;
+    schedule: \"0 3 * * *\";
+    concurrencyPolicy: Forbid;
+    backoffLimit: 2;
+    restartPolicy: OnFailure;
+    image: reporter;
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_service_field_order() {
+        let input = "\
+service api {
+    ports {
+        port: 80;
+        targetPort: 8080;
+    }
+    app: api;
+    type: ClusterIP;
+    labels { tier: backend; }
+    namespace: web;
+}";
+        let expected = "\
+service api {
+    namespace: web;
+    labels {
+        tier: backend;
+    }
+    type: ClusterIP;
+    app: api;
+    ports {
+        port: 80;
+        targetPort: 8080;
+    }
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_config_secret_and_role_field_order() {
+        let input = "\
+config settings { data { mode: fast; } namespace: web; }
+secret creds { data { token: abc; } type: Opaque; annotations { owner: ops; } }
+role reader { rules { pods: [get]; } scope: cluster; }";
+        let expected = "\
+config settings {
+    namespace: web;
+    data {
+        mode: fast;
+    }
+}
+
+secret creds {
+    annotations {
+        owner: ops;
+    }
+    type: Opaque;
+    data {
+        token: abc;
+    }
+}
+
+role reader {
+    scope: cluster;
+    rules {
+        pods: [get];
+    }
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_autoscale_and_disruption() {
+        let input = "\
+deploy app web {
+    autoscale { max: 20; min: 2; cpu: 70%; metrics { queue_depth: external( 30 ); }
+        behavior { scaleUp { pods: 4/15s; select: Max; } } }
+    disruption { maxUnavailable: 25%; }
+    resources { requests { cpu: 100m; } }
+    replicas: 3;
+}";
+        let expected = "\
+deploy app web {
+    replicas: 3;
+    resources {
+        requests {
+            cpu: 100m;
+        }
+    }
+    autoscale {
+        max: 20;
+        min: 2;
+        cpu: 70%;
+        metrics {
+            queue_depth: external(30);
+        }
+        behavior {
+            scaleUp {
+                pods: 4/15s;
+                select: Max;
+            }
+        }
+    }
+    disruption {
+        maxUnavailable: 25%;
+    }
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_network_rules() {
+        let input = "\
+deploy app api {
+    network { allow  from app( frontend ),app(admin,) on http ,9090;
+        allow to cidr(\"10.0.0.0/8\") on 5432; # database
+        deny all egress }
+    image: api;
+}";
+        let expected = "\
+deploy app api {
+    image: api;
+    network {
+        allow from app(frontend), app(admin) on http, 9090;
+        allow to cidr(\"10.0.0.0/8\") on 5432; # database
+        deny all egress;
+    }
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_statements_without_semicolons() {
+        let input = "\
+role reader { rules { pods: [get]; } }
+bind reader to web-sa
+deploy app web {
+    image: web;
+    serviceAccount: web-sa;
+    network {
+        deny all egress
+        allow to app(db) on 5432
+    }
+}";
+        let expected = "\
+role reader {
+    rules {
+        pods: [get];
+    }
+}
+
+bind reader to web-sa;
+
+deploy app web {
+    serviceAccount: web-sa;
+    image: web;
+    network {
+        deny all egress;
+        allow to app(db) on 5432;
+    }
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_roles_and_bindings() {
+        let input = "\
+deploy app web { image: web; serviceAccount: web-sa; namespace: shop; }
+role reader { namespace: shop; rules { pods: [get,list , watch]; } }
+bind  reader to web-sa;
+bind viewer to web-sa; # cluster-wide
+role viewer { scope: cluster; rules { nodes: [ list ]; } }";
+        let expected = "\
+deploy app web {
+    namespace: shop;
+    serviceAccount: web-sa;
+    image: web;
+}
+
+role reader {
+    namespace: shop;
+    rules {
+        pods: [get, list, watch];
+    }
+}
+
+bind reader to web-sa;
+bind viewer to web-sa; # cluster-wide
+
+role viewer {
+    scope: cluster;
+    rules {
+        nodes: [list];
+    }
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_variables() {
+        let input = "\
+let registry = \"ghcr.io/acme\";
+let   tag=v1
+let ns = shop # shared
+deploy app api { namespace: \"${ns}\"; image: \"${registry}/api:${tag}\"; }";
+        let expected = "\
+let registry = \"ghcr.io/acme\";
+let tag = v1;
+let ns = shop; # shared
+
+deploy app api {
+    namespace: \"${ns}\";
+    image: \"${registry}/api:${tag}\";
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_ingress_routes() {
+        let input = "ingress api { host: \"api.example.com\"; tls: secret( api-tls ); routes { \"/\": service(web,http); /admin: service(admin, 80) } }";
+        let expected = "\
+ingress api {
+    host: \"api.example.com\";
+    tls: secret(api-tls);
+    routes {
+        \"/\": service(web, http);
+        /admin: service(admin, 80);
+    }
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_lists() {
+        let input = "deploy app web {\n  args: [ \"--port\",8080 , 'a b', ];\n  command: [\n    /bin/web\n  ]\n  image: web;\n}";
+        let expected = "\
+deploy app web {
+    image: web;
+    command: [/bin/web];
+    args: [\"--port\", 8080, 'a b'];
+}
+";
+        assert_eq!(format(input), expected);
+        assert_eq!(
+            format("deploy app web { args: [] }"),
+            "deploy app web {\n    args: [];\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_format_references() {
+        let input = "deploy app web {\n  envFrom: config( app-config );\n  env { DB: secret(db,password,) }\n}\nsecret db { data { password: x } }";
+        let expected = "\
+deploy app web {
+    env {
+        DB: secret(db, password);
+    }
+    envFrom: config(app-config);
+}
+
+secret db {
+    data {
+        password: x;
+    }
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_keeps_nested_order() {
+        let input =
+            "service web { type: NodePort; ports { port: 443; targetPort: 8443; port: 80; } }";
+        let expected = "\
+service web {
+    type: NodePort;
+    ports {
+        port: 443;
+        targetPort: 8443;
+        port: 80;
+    }
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_documents() {
+        let input = "deploy app a { image: a; } deploy app b { image: b; }\n---\n\nservice a {}";
+        let expected = "\
+deploy app a {
+    image: a;
+}
+
+deploy app b {
+    image: b;
+}
+---
+service a {
+}
+";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_format_keeps_comments() {
+        let input = "\
+# web tier
+deploy app web { // the public site
+  image: nginx /* pinned */;
+  # scale for black friday
+  replicas: 3 # peak
+  ports {
+    http: 80;
+    # more ports soon
+  } // ports
+}
+---  # next document
+// trailing
+";
+        let expected = "\
+# web tier
+deploy app web { // the public site
+    # scale for black friday
+    replicas: 3; # peak
+    image: nginx; /* pinned */
+    ports {
+        http: 80;
+        # more ports soon
+    } // ports
+}
+--- # next document
+// trailing
+";
+        assert_eq!(format(input), expected);
+        assert_eq!(format(expected), expected);
+    }
+
+    #[test]
+    fn test_format_only_comments() {
+        assert_eq!(format("  # nothing yet\n"), "# nothing yet\n");
+    }
+
+    #[test]
+    fn test_format_keeps_string_literals() {
+        let input = "deploy app web {\n  env {\n    GREETING: \"say \\\"hi\\\"\\n\";\n    PATH: 'C:\\bin';\n    CONFIG: \"\"\"\n      a: 1\n      \"\"\";\n  }\n}";
+        let expected = "deploy app web {\n    env {\n        GREETING: \"say \\\"hi\\\"\\n\";\n        PATH: 'C:\\bin';\n        CONFIG: \"\"\"\n      a: 1\n      \"\"\";\n    }\n}\n";
+        assert_eq!(format(input), expected);
+        assert_eq!(
+            parse_source(expected).unwrap().deployments[0].env,
+            parse_source(input).unwrap().deployments[0].env
+        );
+    }
+
+    #[test]
+    fn test_format_is_idempotent() {
+        let input = "deploy app web { env { B: \"2\"; A: 1 } image: x }\n---\nservice web { labels { app: web } }";
+        let once = format(input);
+        assert_eq!(format(&once), once);
+    }
+
+    #[test]
+    fn test_format_rejects_invalid_source() {
+        let errors = format_source("deploy app web { replicas 2; }").unwrap_err();
+        assert_eq!(errors[0].code, codes::UNEXPECTED_TOKEN);
+    }
+
+    #[test]
+    fn test_format_empty_source() {
+        assert_eq!(format(""), "");
+    }
+}
@@ -0,0 +1,280 @@
+use crate::generator::deployment::deployment_labels;
+use crate::generator::yaml::Yaml;
+use crate::nodes::autoscale_node::{AutoscaleNode, MetricSource, ResourceTarget, ScalingRulesNode};
+use crate::nodes::deployment_node::DeploymentNode;
+
+// autoscaler_manifest builds an `autoscaling/v2` HorizontalPodAutoscaler that
+// scales the workload of the given kind. It is named after the workload and
+// carries its labels.
+pub fn autoscaler_manifest(kind: &str, node: &DeploymentNode, autoscale: &AutoscaleNode) -> Yaml {
+    let mut spec = Yaml::map(vec![(
+        "scaleTargetRef",
+        Yaml::map(vec![
+            ("apiVersion", Yaml::from("apps/v1")),
+            ("kind", Yaml::from(kind)),
+            ("name", Yaml::from(&node.name)),
+        ]),
+    )]);
+    if let Some(min) = autoscale.min {
+        spec.insert("minReplicas", Yaml::from(min));
+    }
+    spec.insert("maxReplicas", Yaml::from(autoscale.max));
+
+    let metrics = metrics(autoscale);
+    if !metrics.is_empty() {
+        spec.insert("metrics", Yaml::Seq(metrics));
+    }
+
+    let mut behavior = Yaml::Map(vec![]);
+    for (direction, rules) in [
+        ("scaleUp", &autoscale.scale_up),
+        ("scaleDown", &autoscale.scale_down),
+    ] {
+        if let Some(rules) = rules {
+            behavior.insert(direction, scaling_rules(rules));
+        }
+    }
+    if !behavior.is_empty() {
+        spec.insert("behavior", behavior);
+    }
+
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("autoscaling/v2")),
+        ("kind", Yaml::from("HorizontalPodAutoscaler")),
+        (
+            "metadata",
+            Yaml::map(vec![
+                ("name", Yaml::from(&node.name)),
+                ("namespace", Yaml::from(&node.namespace)),
+                ("labels", deployment_labels(node)),
+            ]),
+        ),
+        ("spec", spec),
+    ])
+}
+
+// metrics renders the cpu and memory targets, then the custom metrics. Without
+// any, Kubernetes scales on an average cpu utilization of 80%.
+fn metrics(autoscale: &AutoscaleNode) -> Vec<Yaml> {
+    let resources = autoscale.resources().map(|(name, target)| {
+        let target = match target {
+            ResourceTarget::Utilization(percent) => Yaml::map(vec![
+                ("type", Yaml::from("Utilization")),
+                ("averageUtilization", Yaml::from(*percent)),
+            ]),
+            ResourceTarget::AverageValue(value) => Yaml::map(vec![
+                ("type", Yaml::from("AverageValue")),
+                ("averageValue", Yaml::from(value.to_string())),
+            ]),
+        };
+        Yaml::map(vec![
+            ("type", Yaml::from("Resource")),
+            (
+                "resource",
+                Yaml::map(vec![("name", Yaml::from(name)), ("target", target)]),
+            ),
+        ])
+    });
+    let custom = autoscale.metrics.iter().map(|(name, metric)| {
+        let key = match metric.source {
+            MetricSource::Pods => "pods",
+            MetricSource::External => "external",
+        };
+        Yaml::map(vec![
+            ("type", Yaml::from(metric.source.as_str())),
+            (
+                key,
+                Yaml::map(vec![
+                    ("metric", Yaml::map(vec![("name", Yaml::from(name))])),
+                    (
+                        "target",
+                        Yaml::map(vec![
+                            ("type", Yaml::from("AverageValue")),
+                            ("averageValue", Yaml::from(metric.target.to_string())),
+                        ]),
+                    ),
+                ]),
+            ),
+        ])
+    });
+    resources.chain(custom).collect()
+}
+
+// scaling_rules renders the behavior of one scaling direction.
+fn scaling_rules(rules: &ScalingRulesNode) -> Yaml {
+    let mut out = Yaml::Map(vec![]);
+    if let Some(window) = rules.stabilization {
+        out.insert("stabilizationWindowSeconds", Yaml::from(window));
+    }
+    if let Some(select) = rules.select {
+        out.insert("selectPolicy", Yaml::from(select.as_str()));
+    }
+    let policies: Vec<Yaml> = [("Pods", rules.pods), ("Percent", rules.percent)]
+        .into_iter()
+        .filter_map(|(kind, policy)| {
+            policy.map(|policy| {
+                Yaml::map(vec![
+                    ("type", Yaml::from(kind)),
+                    ("value", Yaml::from(policy.value)),
+                    ("periodSeconds", Yaml::from(policy.period)),
+                ])
+            })
+        })
+        .collect();
+    if !policies.is_empty() {
+        out.insert("policies", Yaml::Seq(policies));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+    use crate::nodes::autoscale_node::{CustomMetric, ScalingPolicy, SelectPolicy};
+    use crate::nodes::ordered_map::OrderedMap;
+    use crate::nodes::quantity::Quantity;
+
+    fn deployment() -> DeploymentNode {
+        DeploymentNode {
+            name: "web".to_string(),
+            namespace: "shop".to_string(),
+            image: "web:2.0".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_autoscaler_manifest() {
+        let autoscale = AutoscaleNode {
+            min: Some(2),
+            max: 20,
+            cpu: Some(ResourceTarget::Utilization(70)),
+            memory: Some(ResourceTarget::AverageValue(
+                Quantity::parse("512Mi").unwrap(),
+            )),
+            metrics: OrderedMap::from([
+                (
+                    "http_requests_per_second".to_string(),
+                    CustomMetric {
+                        source: MetricSource::Pods,
+                        target: Quantity::parse("100").unwrap(),
+                    },
+                ),
+                (
+                    "queue_messages_ready".to_string(),
+                    CustomMetric {
+                        source: MetricSource::External,
+                        target: Quantity::parse("30").unwrap(),
+                    },
+                ),
+            ]),
+            scale_up: Some(ScalingRulesNode {
+                stabilization: Some(0),
+                pods: Some(ScalingPolicy {
+                    value: 4,
+                    period: 15,
+                }),
+                percent: Some(ScalingPolicy {
+                    value: 100,
+                    period: 15,
+                }),
+                select: Some(SelectPolicy::Max),
+            }),
+            scale_down: Some(ScalingRulesNode {
+                stabilization: Some(300),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let expected = "\
+apiVersion: autoscaling/v2
+kind: HorizontalPodAutoscaler
+metadata:
+  name: web
+  namespace: shop
+  labels:
+    app.kubernetes.io/name: web
+    app.kubernetes.io/version: \"2.0\"
+    app.kubernetes.io/managed-by: kptn
+spec:
+  scaleTargetRef:
+    apiVersion: apps/v1
+    kind: Deployment
+    name: web
+  minReplicas: 2
+  maxReplicas: 20
+  metrics:
+    - type: Resource
+      resource:
+        name: cpu
+        target:
+          type: Utilization
+          averageUtilization: 70
+    - type: Resource
+      resource:
+        name: memory
+        target:
+          type: AverageValue
+          averageValue: 512Mi
+    - type: Pods
+      pods:
+        metric:
+          name: http_requests_per_second
+        target:
+          type: AverageValue
+          averageValue: \"100\"
+    - type: External
+      external:
+        metric:
+          name: queue_messages_ready
+        target:
+          type: AverageValue
+          averageValue: \"30\"
+  behavior:
+    scaleUp:
+      stabilizationWindowSeconds: 0
+      selectPolicy: Max
+      policies:
+        - type: Pods
+          value: 4
+          periodSeconds: 15
+        - type: Percent
+          value: 100
+          periodSeconds: 15
+    scaleDown:
+      stabilizationWindowSeconds: 300
+";
+        assert_eq!(
+            to_yaml_string(&autoscaler_manifest(
+                "Deployment",
+                &deployment(),
+                &autoscale
+            )),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_autoscaler_manifest_minimal() {
+        let autoscale = AutoscaleNode {
+            max: 5,
+            ..Default::default()
+        };
+        let yaml = to_yaml_string(&autoscaler_manifest(
+            "StatefulSet",
+            &deployment(),
+            &autoscale,
+        ));
+        assert!(yaml.ends_with(
+            "\
+spec:
+  scaleTargetRef:
+    apiVersion: apps/v1
+    kind: StatefulSet
+    name: web
+  maxReplicas: 5
+"
+        ));
+    }
+}
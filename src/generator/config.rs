@@ -0,0 +1,120 @@
+use crate::generator::labels::{managed_labels, string_map};
+use crate::generator::yaml::Yaml;
+use crate::nodes::config_node::ConfigNode;
+use crate::nodes::ordered_map::OrderedMap;
+use crate::nodes::secret_node::SecretNode;
+
+// config_map_manifest builds a `v1` ConfigMap from a ConfigNode.
+pub fn config_map_manifest(node: &ConfigNode) -> Yaml {
+    let mut manifest = Yaml::map(vec![
+        ("apiVersion", Yaml::from("v1")),
+        ("kind", Yaml::from("ConfigMap")),
+        (
+            "metadata",
+            metadata(&node.name, &node.namespace, &node.labels, &node.annotations),
+        ),
+    ]);
+    if !node.data.is_empty() {
+        manifest.insert("data", string_map(&node.data));
+    }
+    manifest
+}
+
+// secret_manifest builds a `v1` Secret from a SecretNode. Values are written as
+// `stringData` so that the API server encodes them.
+pub fn secret_manifest(node: &SecretNode) -> Yaml {
+    let mut manifest = Yaml::map(vec![
+        ("apiVersion", Yaml::from("v1")),
+        ("kind", Yaml::from("Secret")),
+        (
+            "metadata",
+            metadata(&node.name, &node.namespace, &node.labels, &node.annotations),
+        ),
+        ("type", Yaml::from(&node.secret_type)),
+    ]);
+    if !node.data.is_empty() {
+        manifest.insert("stringData", string_map(&node.data));
+    }
+    manifest
+}
+
+fn metadata(
+    name: &str,
+    namespace: &str,
+    labels: &OrderedMap<String, String>,
+    annotations: &OrderedMap<String, String>,
+) -> Yaml {
+    let mut metadata = Yaml::map(vec![
+        ("name", Yaml::from(name)),
+        ("namespace", Yaml::from(namespace)),
+        ("labels", managed_labels(labels)),
+    ]);
+    if !annotations.is_empty() {
+        metadata.insert("annotations", string_map(annotations));
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+    use crate::nodes::secret_node::DEFAULT_SECRET_TYPE;
+
+    #[test]
+    fn test_config_map_manifest() {
+        let node = ConfigNode {
+            name: "app-config".to_string(),
+            namespace: "shop".to_string(),
+            data: OrderedMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]),
+            ..Default::default()
+        };
+        let expected = "\
+apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: app-config
+  namespace: shop
+  labels:
+    app.kubernetes.io/managed-by: kptn
+data:
+  LOG_LEVEL: debug
+";
+        assert_eq!(to_yaml_string(&config_map_manifest(&node)), expected);
+    }
+
+    #[test]
+    fn test_secret_manifest() {
+        let node = SecretNode {
+            name: "db-creds".to_string(),
+            namespace: "default".to_string(),
+            secret_type: DEFAULT_SECRET_TYPE.to_string(),
+            annotations: OrderedMap::from([("example.com/rotate".to_string(), "30d".to_string())]),
+            data: OrderedMap::from([("password".to_string(), "hunter2".to_string())]),
+            ..Default::default()
+        };
+        let expected = "\
+apiVersion: v1
+kind: Secret
+metadata:
+  name: db-creds
+  namespace: default
+  labels:
+    app.kubernetes.io/managed-by: kptn
+  annotations:
+    example.com/rotate: \"30d\"
+type: Opaque
+stringData:
+  password: hunter2
+";
+        assert_eq!(to_yaml_string(&secret_manifest(&node)), expected);
+    }
+
+    #[test]
+    fn test_empty_config_and_secret() {
+        let config = to_yaml_string(&config_map_manifest(&ConfigNode::default()));
+        assert!(!config.contains("\ndata:"));
+        let secret = to_yaml_string(&secret_manifest(&SecretNode::default()));
+        assert!(!secret.contains("stringData"));
+    }
+}
@@ -0,0 +1,539 @@
+use crate::generator::labels::{image_version, object_labels, selector_labels, string_map};
+use crate::generator::storage::{pod_volume, volume_mount};
+use crate::generator::yaml::Yaml;
+use crate::nodes::deployment_node::{
+    DeploymentNode, EnvSource, EnvValue, PortRef, ProbeAction, ProbeNode, ResourceRequirementsNode,
+    ResourceSpec,
+};
+use crate::nodes::ordered_map::OrderedMap;
+
+// deployment_labels returns the labels of a deployment and of the objects
+// generated along with it: the recommended labels, the version taken from the
+// image tag and the labels written in the script.
+pub fn deployment_labels(node: &DeploymentNode) -> Yaml {
+    object_labels(&node.name, image_version(&node.image), &node.labels)
+}
+
+// deployment_manifest builds an `apps/v1` Deployment from a DeploymentNode. The
+// pods carry all of the deployment's labels; only the app name selects them.
+// An autoscaled deployment leaves its replica count to the autoscaler.
+pub fn deployment_manifest(node: &DeploymentNode) -> Yaml {
+    let labels = deployment_labels(node);
+    let mut spec = Yaml::Map(vec![]);
+    if node.autoscale.is_none() {
+        spec.insert("replicas", Yaml::from(node.replicas));
+    }
+    spec.insert("selector", workload_selector(node));
+    spec.insert("template", pod_template(node, labels.clone(), true));
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("apps/v1")),
+        ("kind", Yaml::from("Deployment")),
+        ("metadata", workload_metadata(node, labels)),
+        ("spec", spec),
+    ])
+}
+
+// workload_metadata builds the name, namespace, labels and annotations of a
+// workload object.
+pub fn workload_metadata(node: &DeploymentNode, labels: Yaml) -> Yaml {
+    let mut metadata = Yaml::map(vec![
+        ("name", Yaml::from(&node.name)),
+        ("namespace", Yaml::from(&node.namespace)),
+        ("labels", labels),
+    ]);
+    if !node.annotations.is_empty() {
+        metadata.insert("annotations", string_map(&node.annotations));
+    }
+    metadata
+}
+
+// workload_selector selects the pods of a workload by its name only.
+pub fn workload_selector(node: &DeploymentNode) -> Yaml {
+    Yaml::map(vec![("matchLabels", selector_labels(&node.name))])
+}
+
+// pod_template builds the pod template of a workload. With `claim_volumes` the
+// storage claim is mounted as a pod volume; a StatefulSet leaves it out because
+// the claim comes from its volume claim template.
+pub fn pod_template(node: &DeploymentNode, labels: Yaml, claim_volumes: bool) -> Yaml {
+    let mut spec = Yaml::Map(vec![]);
+    if let Some(service_account) = &node.service_account {
+        spec.insert("serviceAccountName", Yaml::from(service_account));
+    }
+    spec.insert("containers", Yaml::Seq(vec![container(node)]));
+    if let Some(storage) = node.storage.as_ref().filter(|_| claim_volumes) {
+        spec.insert("volumes", Yaml::Seq(vec![pod_volume(storage)]));
+    }
+    Yaml::map(vec![
+        ("metadata", Yaml::map(vec![("labels", labels)])),
+        ("spec", spec),
+    ])
+}
+
+// container builds the single container of the pod template.
+fn container(node: &DeploymentNode) -> Yaml {
+    let mut container = Yaml::map(vec![
+        ("name", Yaml::from(&node.name)),
+        ("image", Yaml::from(&node.image)),
+    ]);
+
+    if !node.command.is_empty() {
+        container.insert(
+            "command",
+            Yaml::Seq(node.command.iter().map(Yaml::from).collect()),
+        );
+    }
+
+    if !node.args.is_empty() {
+        container.insert(
+            "args",
+            Yaml::Seq(node.args.iter().map(Yaml::from).collect()),
+        );
+    }
+
+    if !node.env.is_empty() {
+        let env = node
+            .env
+            .iter()
+            .map(|(name, value)| env_var(name, value))
+            .collect();
+        container.insert("env", Yaml::Seq(env));
+    }
+
+    if !node.env_from.is_empty() {
+        let sources = node
+            .env_from
+            .iter()
+            .map(|source| match source {
+                EnvSource::Config(name) => Yaml::map(vec![(
+                    "configMapRef",
+                    Yaml::map(vec![("name", Yaml::from(name))]),
+                )]),
+                EnvSource::Secret(name) => Yaml::map(vec![(
+                    "secretRef",
+                    Yaml::map(vec![("name", Yaml::from(name))]),
+                )]),
+            })
+            .collect();
+        container.insert("envFrom", Yaml::Seq(sources));
+    }
+
+    if !node.ports.is_empty() {
+        let ports = node
+            .ports
+            .iter()
+            .map(|(name, port)| {
+                Yaml::map(vec![
+                    ("name", Yaml::from(name)),
+                    ("containerPort", Yaml::from(*port)),
+                ])
+            })
+            .collect();
+        container.insert("ports", Yaml::Seq(ports));
+    }
+
+    if let Some(resources) = &node.resources {
+        let resources = resource_requirements(resources);
+        if !resources.is_empty() {
+            container.insert("resources", resources);
+        }
+    }
+
+    if let Some(storage) = &node.storage {
+        container.insert("volumeMounts", Yaml::Seq(vec![volume_mount(storage)]));
+    }
+
+    for (kind, probe) in node.probes.iter() {
+        container.insert(format!("{}Probe", kind), probe_spec(probe, &node.ports));
+    }
+
+    container
+}
+
+// env_var renders an env entry, with references to configs and secrets as `valueFrom`.
+fn env_var(name: &str, value: &EnvValue) -> Yaml {
+    let key_ref = |kind: &str, object: &str, key: &str| {
+        Yaml::map(vec![(
+            kind,
+            Yaml::map(vec![("name", Yaml::from(object)), ("key", Yaml::from(key))]),
+        )])
+    };
+    let mut var = Yaml::map(vec![("name", Yaml::from(name))]);
+    match value {
+        EnvValue::Value(value) => var.insert("value", Yaml::from(value)),
+        EnvValue::ConfigKey { config, key } => {
+            var.insert("valueFrom", key_ref("configMapKeyRef", config, key))
+        }
+        EnvValue::SecretKey { secret, key } => {
+            var.insert("valueFrom", key_ref("secretKeyRef", secret, key))
+        }
+    }
+    var
+}
+
+// probe_spec renders a probe. Named ports are resolved to their number, as
+// grpc probes only accept numbers; a name that does not resolve is kept.
+fn probe_spec(probe: &ProbeNode, ports: &OrderedMap<String, i32>) -> Yaml {
+    let port = |port: &PortRef| match port.resolve(ports) {
+        Some(number) => Yaml::from(number),
+        None => match port {
+            PortRef::Name(name) => Yaml::from(name),
+            PortRef::Number(number) => Yaml::from(*number),
+        },
+    };
+    let mut spec = match &probe.action {
+        ProbeAction::Http { path, port: p } => Yaml::map(vec![(
+            "httpGet",
+            Yaml::map(vec![("path", Yaml::from(path)), ("port", port(p))]),
+        )]),
+        ProbeAction::Tcp { port: p } => {
+            Yaml::map(vec![("tcpSocket", Yaml::map(vec![("port", port(p))]))])
+        }
+        ProbeAction::Exec { command } => Yaml::map(vec![(
+            "exec",
+            Yaml::map(vec![(
+                "command",
+                Yaml::Seq(command.iter().map(Yaml::from).collect()),
+            )]),
+        )]),
+        ProbeAction::Grpc { port: p, service } => {
+            let mut grpc = Yaml::map(vec![("port", port(p))]);
+            if let Some(service) = service {
+                grpc.insert("service", Yaml::from(service));
+            }
+            Yaml::map(vec![("grpc", grpc)])
+        }
+    };
+    for (key, value) in [
+        ("initialDelaySeconds", probe.initial_delay),
+        ("periodSeconds", probe.period),
+        ("timeoutSeconds", probe.timeout),
+        ("failureThreshold", probe.failure_threshold),
+        ("successThreshold", probe.success_threshold),
+    ] {
+        if let Some(value) = value {
+            spec.insert(key, Yaml::from(value));
+        }
+    }
+    spec
+}
+
+// resource_requirements renders limits and requests, skipping unset values.
+fn resource_requirements(resources: &ResourceRequirementsNode) -> Yaml {
+    let mut out = Yaml::Map(vec![]);
+    for (key, spec) in [
+        ("limits", &resources.limits),
+        ("requests", &resources.requests),
+    ] {
+        let spec = resource_spec(spec);
+        if !spec.is_empty() {
+            out.insert(key, spec);
+        }
+    }
+    out
+}
+
+fn resource_spec(spec: &ResourceSpec) -> Yaml {
+    let mut out = Yaml::Map(vec![]);
+    if let Some(memory) = &spec.memory {
+        out.insert("memory", Yaml::from(memory.to_string()));
+    }
+    if let Some(cpu) = &spec.cpu {
+        out.insert("cpu", Yaml::from(cpu.to_string()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+    use crate::lexer::lexer::Span;
+    use crate::nodes::autoscale_node::AutoscaleNode;
+    use crate::nodes::deployment_node::{ProbesNode, StorageConfigNode};
+    use crate::nodes::field_spans::FieldSpans;
+    use crate::nodes::quantity::Quantity;
+
+    #[test]
+    fn test_deployment_manifest() {
+        let node = DeploymentNode {
+            name: "my-app".to_string(),
+            namespace: "default".to_string(),
+            labels: OrderedMap::from([("tier".to_string(), "backend".to_string())]),
+            annotations: OrderedMap::from([("example.com/owner".to_string(), "team".to_string())]),
+            replicas: 3,
+            image: "my-app:v1.0".to_string(),
+            command: vec!["/bin/my-app".to_string()],
+            args: vec!["--port".to_string(), "8080".to_string()],
+            env: OrderedMap::from([(
+                "LOG_LEVEL".to_string(),
+                EnvValue::Value("debug".to_string()),
+            )]),
+            env_from: Vec::new(),
+            ports: OrderedMap::from([("http".to_string(), 8080)]),
+            resources: Some(ResourceRequirementsNode {
+                limits: ResourceSpec {
+                    memory: Quantity::parse("512Mi"),
+                    cpu: Quantity::parse("500m"),
+                },
+                requests: ResourceSpec {
+                    memory: Quantity::parse("256Mi"),
+                    cpu: None,
+                },
+            }),
+            storage: None,
+            probes: ProbesNode::default(),
+            autoscale: None,
+            disruption: None,
+            network: None,
+            service_account: None,
+            fields: FieldSpans::new(),
+            span: Span::default(),
+        };
+
+        let expected = "\
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: my-app
+  namespace: default
+  labels:
+    app.kubernetes.io/name: my-app
+    app.kubernetes.io/version: v1.0
+    app.kubernetes.io/managed-by: kptn
+    tier: backend
+  annotations:
+    example.com/owner: team
+spec:
+  replicas: 3
+  selector:
+    matchLabels:
+      app.kubernetes.io/name: my-app
+  template:
+    metadata:
+      labels:
+        app.kubernetes.io/name: my-app
+        app.kubernetes.io/version: v1.0
+        app.kubernetes.io/managed-by: kptn
+        tier: backend
+    spec:
+      containers:
+        - name: my-app
+          image: my-app:v1.0
+          command:
+            - /bin/my-app
+          args:
+            - --port
+            - \"8080\"
+          env:
+            - name: LOG_LEVEL
+              value: debug
+          ports:
+            - name: http
+              containerPort: 8080
+          resources:
+            limits:
+              memory: 512Mi
+              cpu: 500m
+            requests:
+              memory: 256Mi
+";
+        assert_eq!(to_yaml_string(&deployment_manifest(&node)), expected);
+    }
+
+    #[test]
+    fn test_deployment_manifest_omits_empty_sections() {
+        let node = DeploymentNode {
+            name: "bare".to_string(),
+            namespace: "default".to_string(),
+            replicas: 1,
+            image: "nginx".to_string(),
+            resources: Some(ResourceRequirementsNode::default()),
+            ..Default::default()
+        };
+        let yaml = to_yaml_string(&deployment_manifest(&node));
+        assert!(!yaml.contains("command"));
+        assert!(!yaml.contains("args"));
+        assert!(!yaml.contains("env"));
+        assert!(!yaml.contains("ports"));
+        assert!(!yaml.contains("resources"));
+        assert!(!yaml.contains("volumes"));
+        assert!(!yaml.contains("Probe"));
+    }
+
+    #[test]
+    fn test_deployment_manifest_env_references() {
+        let node = DeploymentNode {
+            env: OrderedMap::from([(
+                "DB_PASS".to_string(),
+                EnvValue::SecretKey {
+                    secret: "db-creds".to_string(),
+                    key: "password".to_string(),
+                },
+            )]),
+            env_from: vec![
+                EnvSource::Config("app-config".to_string()),
+                EnvSource::Secret("api-keys".to_string()),
+            ],
+            ..Default::default()
+        };
+        let yaml = to_yaml_string(&deployment_manifest(&node));
+        assert!(yaml.contains(
+            "          env:
+            - name: DB_PASS
+              valueFrom:
+                secretKeyRef:
+                  name: db-creds
+                  key: password
+          envFrom:
+            - configMapRef:
+                name: app-config
+            - secretRef:
+                name: api-keys
+"
+        ));
+
+        let node = DeploymentNode {
+            env: OrderedMap::from([(
+                "LOG_LEVEL".to_string(),
+                EnvValue::ConfigKey {
+                    config: "app-config".to_string(),
+                    key: "level".to_string(),
+                },
+            )]),
+            ..Default::default()
+        };
+        assert!(to_yaml_string(&deployment_manifest(&node)).contains(
+            "              valueFrom:
+                configMapKeyRef:
+                  name: app-config
+                  key: level
+"
+        ));
+    }
+
+    #[test]
+    fn test_deployment_manifest_probes() {
+        let probe = |action| ProbeNode {
+            action,
+            initial_delay: None,
+            period: None,
+            timeout: None,
+            failure_threshold: None,
+            success_threshold: None,
+            span: Span::default(),
+        };
+        let node = DeploymentNode {
+            name: "api".to_string(),
+            image: "api".to_string(),
+            ports: OrderedMap::from([("http".to_string(), 8080), ("grpc".to_string(), 9090)]),
+            probes: ProbesNode {
+                liveness: Some(ProbeNode {
+                    period: Some(10),
+                    failure_threshold: Some(3),
+                    ..probe(ProbeAction::Http {
+                        path: "/healthz".to_string(),
+                        port: PortRef::Name("http".to_string()),
+                    })
+                }),
+                readiness: Some(probe(ProbeAction::Grpc {
+                    port: PortRef::Name("grpc".to_string()),
+                    service: Some("ready".to_string()),
+                })),
+                startup: Some(probe(ProbeAction::Exec {
+                    command: vec!["cat".to_string(), "/tmp/started".to_string()],
+                })),
+            },
+            ..Default::default()
+        };
+        let yaml = to_yaml_string(&deployment_manifest(&node));
+        assert!(yaml.ends_with(
+            "          livenessProbe:
+            httpGet:
+              path: /healthz
+              port: 8080
+            periodSeconds: 10
+            failureThreshold: 3
+          readinessProbe:
+            grpc:
+              port: 9090
+              service: ready
+          startupProbe:
+            exec:
+              command:
+                - cat
+                - /tmp/started
+"
+        ));
+
+        let tcp = DeploymentNode {
+            probes: ProbesNode {
+                liveness: Some(probe(ProbeAction::Tcp {
+                    port: PortRef::Number(5432),
+                })),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(to_yaml_string(&deployment_manifest(&tcp))
+            .ends_with("livenessProbe:\n            tcpSocket:\n              port: 5432\n"));
+    }
+
+    #[test]
+    fn test_deployment_manifest_service_account() {
+        let node = DeploymentNode {
+            name: "web".to_string(),
+            namespace: "default".to_string(),
+            image: "web".to_string(),
+            service_account: Some("web-sa".to_string()),
+            ..Default::default()
+        };
+        let yaml = to_yaml_string(&deployment_manifest(&node));
+        assert!(yaml.contains("    spec:\n      serviceAccountName: web-sa\n      containers:\n"));
+    }
+
+    #[test]
+    fn test_deployment_manifest_autoscaled() {
+        let node = DeploymentNode {
+            name: "web".to_string(),
+            namespace: "default".to_string(),
+            replicas: 3,
+            autoscale: Some(AutoscaleNode {
+                max: 10,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let yaml = to_yaml_string(&deployment_manifest(&node));
+        assert!(!yaml.contains("replicas"));
+        assert!(yaml.contains("spec:\n  selector:\n"));
+    }
+
+    #[test]
+    fn test_deployment_manifest_mounts_storage() {
+        let node = DeploymentNode {
+            name: "db".to_string(),
+            namespace: "default".to_string(),
+            replicas: 1,
+            image: "postgres:16".to_string(),
+            storage: Some(StorageConfigNode {
+                volume: "db-data".to_string(),
+                size: Quantity::parse("10Gi").unwrap(),
+                mount_path: "/var/lib/postgresql".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let yaml = to_yaml_string(&deployment_manifest(&node));
+        assert!(yaml.contains(
+            "          volumeMounts:
+            - name: db-data
+              mountPath: /var/lib/postgresql
+      volumes:
+        - name: db-data
+          persistentVolumeClaim:
+            claimName: db-data
+"
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::generator::deployment::{deployment_labels, workload_selector};
+use crate::generator::yaml::Yaml;
+use crate::nodes::deployment_node::DeploymentNode;
+use crate::nodes::disruption_node::{DisruptionNode, DisruptionValue};
+
+// disruption_budget_manifest builds a `policy/v1` PodDisruptionBudget that
+// guards the pods of a workload. It is named after the workload and selects its
+// pods the same way the workload does.
+pub fn disruption_budget_manifest(node: &DeploymentNode, disruption: &DisruptionNode) -> Yaml {
+    let value = match disruption.budget.value() {
+        DisruptionValue::Count(count) => Yaml::from(count),
+        percent => Yaml::from(percent.to_string()),
+    };
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("policy/v1")),
+        ("kind", Yaml::from("PodDisruptionBudget")),
+        (
+            "metadata",
+            Yaml::map(vec![
+                ("name", Yaml::from(&node.name)),
+                ("namespace", Yaml::from(&node.namespace)),
+                ("labels", deployment_labels(node)),
+            ]),
+        ),
+        (
+            "spec",
+            Yaml::map(vec![
+                (disruption.budget.field(), value),
+                ("selector", workload_selector(node)),
+            ]),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+    use crate::lexer::lexer::Span;
+    use crate::nodes::disruption_node::DisruptionBudget;
+
+    fn deployment() -> DeploymentNode {
+        DeploymentNode {
+            name: "web".to_string(),
+            namespace: "shop".to_string(),
+            image: "web:2.0".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_disruption_budget_manifest() {
+        let disruption = DisruptionNode {
+            budget: DisruptionBudget::MinAvailable(DisruptionValue::Count(1)),
+            span: Span::new(0, 10),
+        };
+        let expected = "\
+apiVersion: policy/v1
+kind: PodDisruptionBudget
+metadata:
+  name: web
+  namespace: shop
+  labels:
+    app.kubernetes.io/name: web
+    app.kubernetes.io/version: \"2.0\"
+    app.kubernetes.io/managed-by: kptn
+spec:
+  minAvailable: 1
+  selector:
+    matchLabels:
+      app.kubernetes.io/name: web
+";
+        assert_eq!(
+            to_yaml_string(&disruption_budget_manifest(&deployment(), &disruption)),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_disruption_budget_manifest_percent() {
+        let disruption = DisruptionNode {
+            budget: DisruptionBudget::MaxUnavailable(DisruptionValue::Percent(25)),
+            span: Span::new(0, 10),
+        };
+        let yaml = to_yaml_string(&disruption_budget_manifest(&deployment(), &disruption));
+        assert!(yaml.contains("spec:\n  maxUnavailable: 25%\n  selector:\n"));
+    }
+}
@@ -0,0 +1,500 @@
+use crate::generator::autoscale::autoscaler_manifest;
+use crate::generator::config::{config_map_manifest, secret_manifest};
+use crate::generator::deployment::{deployment_labels, deployment_manifest};
+use crate::generator::disruption::disruption_budget_manifest;
+use crate::generator::ingress::ingress_manifest;
+use crate::generator::job::{cronjob_manifest, job_manifest};
+use crate::generator::namespace::{is_builtin_namespace, namespace_manifest};
+use crate::generator::network::network_policy_manifest;
+use crate::generator::rbac::{
+    binding_manifests, role_manifest, service_account_manifest, service_accounts,
+};
+use crate::generator::service::service_manifest;
+use crate::generator::statefulset::statefulset_manifest;
+use crate::generator::storage::persistent_volume_claim;
+use crate::generator::yaml::{to_yaml_stream, Yaml};
+use crate::nodes::rbac_node::RoleScope;
+use crate::parser::parser::Program;
+
+// Order in which kinds are applied, so that every object comes after the
+// objects it depends on.
+const KIND_ORDER: [&str; 18] = [
+    "Namespace",
+    "ServiceAccount",
+    "ClusterRole",
+    "Role",
+    "ClusterRoleBinding",
+    "RoleBinding",
+    "ConfigMap",
+    "Secret",
+    "PersistentVolumeClaim",
+    "Deployment",
+    "StatefulSet",
+    "Job",
+    "CronJob",
+    "Service",
+    "Ingress",
+    "HorizontalPodAutoscaler",
+    "PodDisruptionBudget",
+    "NetworkPolicy",
+];
+
+// manifests builds the Kubernetes objects for every node of the program, sorted
+// by KIND_ORDER. Objects of the same kind keep their source order. A Namespace
+// is generated for every namespace that is not built into the cluster.
+pub fn manifests(program: &Program) -> Vec<Yaml> {
+    let mut manifests: Vec<Yaml> = namespaces(program)
+        .iter()
+        .map(|name| namespace_manifest(name))
+        .collect();
+    manifests.extend(
+        service_accounts(program)
+            .into_iter()
+            .map(|(namespace, name)| service_account_manifest(namespace, name)),
+    );
+    manifests.extend(program.roles.iter().map(role_manifest));
+    for binding in &program.bindings {
+        manifests.extend(binding_manifests(binding, program));
+    }
+    manifests.extend(program.configs.iter().map(config_map_manifest));
+    manifests.extend(program.secrets.iter().map(secret_manifest));
+    let claim_owners = program
+        .deployments
+        .iter()
+        .chain(program.jobs.iter().map(|j| &j.deployment))
+        .chain(program.cronjobs.iter().map(|c| &c.job.deployment));
+    for owner in claim_owners {
+        if let Some(storage) = &owner.storage {
+            manifests.push(persistent_volume_claim(
+                storage,
+                &owner.namespace,
+                deployment_labels(owner),
+            ));
+        }
+    }
+    manifests.extend(program.deployments.iter().map(deployment_manifest));
+    manifests.extend(program.statefulsets.iter().map(statefulset_manifest));
+    manifests.extend(program.jobs.iter().map(job_manifest));
+    manifests.extend(program.cronjobs.iter().map(cronjob_manifest));
+    manifests.extend(program.services.iter().map(service_manifest));
+    manifests.extend(
+        program
+            .ingresses
+            .iter()
+            .map(|ingress| ingress_manifest(ingress, program)),
+    );
+    for (kind, workload) in program.workloads() {
+        if let Some(autoscale) = &workload.autoscale {
+            let kind = match kind {
+                "statefulset" => "StatefulSet",
+                _ => "Deployment",
+            };
+            manifests.push(autoscaler_manifest(kind, workload, autoscale));
+        }
+        if let Some(disruption) = &workload.disruption {
+            manifests.push(disruption_budget_manifest(workload, disruption));
+        }
+        if let Some(network) = &workload.network {
+            manifests.push(network_policy_manifest(workload, network, program));
+        }
+    }
+    manifests.sort_by_key(kind_rank);
+    manifests
+}
+
+// namespaces lists the non-builtin namespaces used by the program, once each,
+// in order of first use.
+fn namespaces(program: &Program) -> Vec<&str> {
+    let used = program
+        .deployments
+        .iter()
+        .map(|d| d.namespace.as_str())
+        .chain(
+            program
+                .statefulsets
+                .iter()
+                .map(|s| s.deployment.namespace.as_str()),
+        )
+        .chain(program.jobs.iter().map(|j| j.deployment.namespace.as_str()))
+        .chain(
+            program
+                .cronjobs
+                .iter()
+                .map(|c| c.job.deployment.namespace.as_str()),
+        )
+        .chain(program.services.iter().map(|s| s.namespace.as_str()))
+        .chain(program.configs.iter().map(|c| c.namespace.as_str()))
+        .chain(program.secrets.iter().map(|s| s.namespace.as_str()))
+        .chain(program.ingresses.iter().map(|i| i.namespace.as_str()))
+        .chain(
+            program
+                .roles
+                .iter()
+                .filter(|r| r.scope == RoleScope::Namespace)
+                .map(|r| r.namespace.as_str()),
+        )
+        .chain(
+            service_accounts(program)
+                .into_iter()
+                .map(|(namespace, _)| namespace),
+        );
+    let mut namespaces: Vec<&str> = Vec::new();
+    for namespace in used {
+        if !is_builtin_namespace(namespace) && !namespaces.contains(&namespace) {
+            namespaces.push(namespace);
+        }
+    }
+    namespaces
+}
+
+// kind_rank returns the position of a manifest's kind in KIND_ORDER.
+fn kind_rank(manifest: &Yaml) -> usize {
+    let kind = match manifest.get("kind") {
+        Some(Yaml::Str(kind)) => kind.as_str(),
+        _ => "",
+    };
+    KIND_ORDER
+        .iter()
+        .position(|k| *k == kind)
+        .unwrap_or(KIND_ORDER.len())
+}
+
+// generate renders the program as a YAML stream ready to be applied.
+pub fn generate(program: &Program) -> String {
+    to_yaml_stream(&manifests(program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parser::parse_source;
+
+    fn generate_from(input: &str) -> String {
+        let program = parse_source(input).unwrap();
+        generate(&program)
+    }
+
+    #[test]
+    fn test_generate_single_deployment() {
+        let output = generate_from("deploy app web {\n    image: \"nginx:1.25\";\n}");
+        assert!(output.starts_with("apiVersion: apps/v1\nkind: Deployment\n"));
+        assert!(output.contains("  replicas: 1\n"));
+        assert!(output.contains("image: nginx:1.25\n"));
+    }
+
+    #[test]
+    fn test_generate_multiple_deployments() {
+        let output = generate_from("deploy app a {\n}\ndeploy app b {\n}");
+        assert_eq!(output.matches("kind: Deployment").count(), 2);
+        assert!(output.contains("\n---\n"));
+    }
+
+    #[test]
+    fn test_generate_storage_claim() {
+        let output = generate_from(include_str!("../../examples/basic_app.kp"));
+        let documents: Vec<&str> = output.split("---\n").collect();
+        assert_eq!(documents.len(), 3);
+        assert!(documents[0].contains("kind: Secret\n"));
+        assert!(documents[1].contains("kind: PersistentVolumeClaim\n"));
+        assert!(documents[1].contains("storage: 5Gi\n"));
+        assert!(documents[2].contains("kind: Deployment\n"));
+        assert!(documents[2].contains("claimName: my-app-data\n"));
+        assert!(documents[2].contains("mountPath: /var/lib/my-app\n"));
+        assert!(documents[2].contains("secretKeyRef:\n                  name: my-app-db\n"));
+    }
+
+    #[test]
+    fn test_generate_service() {
+        let output = generate_from(include_str!("../../examples/web_service.kp"));
+        let documents: Vec<&str> = output.split("---\n").collect();
+        assert_eq!(documents.len(), 2);
+        assert!(documents[0].contains("kind: Deployment\n"));
+        assert!(documents[1].contains("kind: Service\n"));
+        assert!(documents[1].contains("  type: NodePort\n"));
+        assert!(documents[0].contains("    matchLabels:\n      app.kubernetes.io/name: web\n"));
+        assert!(documents[1].contains("  selector:\n    app.kubernetes.io/name: web\n"));
+    }
+
+    #[test]
+    fn test_generate_orders_documents_by_kind() {
+        let input = "\
+service web {
+    namespace: shop;
+    ports { port: 80; }
+}
+---
+deploy app web {
+    namespace: shop;
+    image: \"nginx\";
+    storage { volume: web-data; size: 1Gi; mountPath: /data; }
+}
+---
+deploy app worker {
+    namespace: shop;
+}
+secret worker-token { namespace: shop; }
+config web-config { namespace: shop; }
+";
+        let output = generate_from(input);
+        let kinds: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("kind: "))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "Namespace",
+                "ConfigMap",
+                "Secret",
+                "PersistentVolumeClaim",
+                "Deployment",
+                "Deployment",
+                "Service"
+            ]
+        );
+        assert_eq!(output.matches("\n---\n").count(), 6);
+        assert!(output.starts_with("apiVersion: v1\nkind: Namespace\nmetadata:\n  name: shop\n"));
+        assert!(output.find("name: web\n").unwrap() < output.find("name: worker\n").unwrap());
+    }
+
+    #[test]
+    fn test_generate_statefulset() {
+        let input = "\
+service db { namespace: data; type: headless; ports { port: 5432; } }
+deploy statefulset db {
+    namespace: data;
+    image: postgres;
+    storage { volume: pgdata; size: 10Gi; mountPath: /data; }
+}
+deploy app web { }";
+        let output = generate_from(input);
+        let kinds: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("kind: "))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec!["Namespace", "Deployment", "StatefulSet", "Service"]
+        );
+        assert!(output.contains("  volumeClaimTemplates:\n    - metadata:\n        name: pgdata\n"));
+    }
+
+    #[test]
+    fn test_generate_jobs() {
+        let input = "\
+cronjob report {
+    namespace: batch;
+    schedule: \"@daily\";
+    image: reporter;
+    storage { volume: reports; size: 1Gi; mountPath: /reports; }
+}
+job migrate { image: api; }
+service api { }";
+        let output = generate_from(input);
+        let kinds: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("kind: "))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "Namespace",
+                "PersistentVolumeClaim",
+                "Job",
+                "CronJob",
+                "Service"
+            ]
+        );
+        assert!(output.contains("  schedule: \"@daily\"\n"));
+        assert!(output.contains("claimName: reports\n"));
+    }
+
+    #[test]
+    fn test_generate_ingress() {
+        let input = "\
+ingress web { namespace: shop; routes { /: service(web, http); } }
+service web { namespace: shop; ports { port: 80; targetPort: 8080; } }
+deploy app web { namespace: shop; ports { http: 8080; } }";
+        let output = generate_from(input);
+        let kinds: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("kind: "))
+            .collect();
+        assert_eq!(kinds, vec!["Namespace", "Deployment", "Service", "Ingress"]);
+        assert!(output.ends_with("                port:\n                  number: 80\n"));
+    }
+
+    #[test]
+    fn test_generate_autoscaler() {
+        let input = "\
+deploy app web {
+    replicas: 3;
+    autoscale { min: 2; max: 20; cpu: 70%; }
+}
+deploy statefulset db { autoscale { max: 3; } }
+deploy app worker { replicas: 2; }";
+        let output = generate_from(input);
+        let kinds: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("kind: "))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "Deployment",
+                "Deployment",
+                "StatefulSet",
+                "HorizontalPodAutoscaler",
+                "HorizontalPodAutoscaler"
+            ]
+        );
+        assert_eq!(output.matches("  replicas: ").count(), 1);
+        assert!(output.contains("  replicas: 2\n"));
+        assert!(output.contains("    kind: StatefulSet\n    name: db\n"));
+    }
+
+    #[test]
+    fn test_generate_disruption_budget() {
+        let input = "\
+deploy app web { disruption { maxUnavailable: 25%; } autoscale { max: 4; } }
+deploy statefulset db { disruption { minAvailable: 2; } }
+service web { app: web; ports { port: 80; } }";
+        let output = generate_from(input);
+        let kinds: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("kind: "))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "Deployment",
+                "StatefulSet",
+                "Service",
+                "HorizontalPodAutoscaler",
+                "PodDisruptionBudget",
+                "PodDisruptionBudget"
+            ]
+        );
+        assert!(output.contains("  maxUnavailable: 25%\n"));
+        assert!(output.contains(
+            "  minAvailable: 2\n  selector:\n    matchLabels:\n      app.kubernetes.io/name: db\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_network_policies() {
+        let input = "\
+job migrate { network { allow to app(db) on sql; } }
+deploy statefulset db { ports { sql: 5432; } network { allow from app(migrate); } }
+service db { type: headless; }";
+        let output = generate_from(input);
+        let kinds: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("kind: "))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "StatefulSet",
+                "Job",
+                "Service",
+                "NetworkPolicy",
+                "NetworkPolicy"
+            ]
+        );
+        assert!(output.contains("  name: db\n  namespace: default\n  labels:\n    app.kubernetes.io/name: db\n    app.kubernetes.io/managed-by: kptn\nspec:\n  podSelector:\n"));
+        assert!(output.contains("        - protocol: TCP\n          port: 5432\n"));
+    }
+
+    #[test]
+    fn test_generate_rbac() {
+        let input = "\
+deploy app web { namespace: shop; serviceAccount: web-sa; }
+bind reader to web-sa;
+role reader { namespace: shop; rules { configmaps: [get]; } }
+role node-viewer { scope: cluster; rules { nodes: [list]; } }
+bind node-viewer to ops;";
+        let output = generate_from(input);
+        let kinds: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("kind: "))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "Namespace",
+                "ServiceAccount",
+                "ServiceAccount",
+                "ClusterRole",
+                "Role",
+                "ClusterRoleBinding",
+                "RoleBinding",
+                "Deployment"
+            ]
+        );
+        assert!(output.contains("      serviceAccountName: web-sa\n"));
+        assert!(
+            output.contains("kind: ServiceAccount\nmetadata:\n  name: ops\n  namespace: default\n")
+        );
+        assert_eq!(output.matches("kind: Namespace\n").count(), 1);
+    }
+
+    #[test]
+    fn test_generate_skips_builtin_namespaces() {
+        let output = generate_from("deploy app a {\n}\ndeploy app b { namespace: kube-system; }");
+        assert!(!output.contains("kind: Namespace"));
+    }
+
+    #[test]
+    fn test_generate_string_literals() {
+        let input = "deploy app web {\n    image: 'nginx';\n    env {\n        MOTD: \"caf\\u{e9} \\\"open\\\"\";\n        CONFIG: \"\"\"\n            a: 1\n            b: [x; y]\n            \"\"\";\n    }\n}";
+        let output = generate_from(input);
+        assert!(output.contains("image: nginx\n"));
+        assert!(output.contains("value: café \"open\"\n"));
+        assert!(output.contains("value: |\n                a: 1\n                b: [x; y]\n"));
+    }
+
+    #[test]
+    fn test_generate_keeps_source_order() {
+        let input = "\
+deploy app web {
+    labels { tier: web; env: prod; }
+    ports { metrics: 9090; http: 8080; admin: 9000; }
+    env { ZONE: a; API_URL: b; MODE: c; }
+}
+service web {
+    ports { port: 443; targetPort: 8443; port: 80; targetPort: 8080; }
+}";
+        let output = generate_from(input);
+        let names: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix("- name: "))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "web", "ZONE", "API_URL", "MODE", "metrics", "http", "admin", "port-443", "port-80"
+            ]
+        );
+        assert!(output.contains("    tier: web\n    env: prod\n"));
+    }
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        let examples = [
+            include_str!("../../examples/basic_app.kp"),
+            include_str!("../../examples/web_service.kp"),
+        ];
+        for example in examples {
+            let first = generate_from(example);
+            for _ in 0..20 {
+                assert_eq!(generate_from(example), first);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_empty_program() {
+        assert_eq!(generate(&Program::default()), "");
+    }
+}
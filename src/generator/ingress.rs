@@ -0,0 +1,225 @@
+use crate::generator::labels::{managed_labels, string_map};
+use crate::generator::yaml::Yaml;
+use crate::nodes::deployment_node::PortRef;
+use crate::nodes::ingress_node::{IngressBackend, IngressNode};
+use crate::parser::parser::Program;
+
+// ingress_manifest builds a `networking.k8s.io/v1` Ingress from an IngressNode.
+// Every route matches its path as a prefix. The TLS certificate, when set,
+// covers the ingress host.
+pub fn ingress_manifest(node: &IngressNode, program: &Program) -> Yaml {
+    let mut metadata = Yaml::map(vec![
+        ("name", Yaml::from(&node.name)),
+        ("namespace", Yaml::from(&node.namespace)),
+        ("labels", managed_labels(&node.labels)),
+    ]);
+    if !node.annotations.is_empty() {
+        metadata.insert("annotations", string_map(&node.annotations));
+    }
+
+    let mut spec = Yaml::Map(vec![]);
+    if let Some(secret) = &node.tls_secret {
+        let mut tls = Yaml::Map(vec![]);
+        if let Some(host) = &node.host {
+            tls.insert("hosts", Yaml::Seq(vec![Yaml::from(host)]));
+        }
+        tls.insert("secretName", Yaml::from(secret));
+        spec.insert("tls", Yaml::Seq(vec![tls]));
+    }
+
+    let paths = node
+        .routes
+        .iter()
+        .map(|(path, backend)| {
+            Yaml::map(vec![
+                ("path", Yaml::from(path)),
+                ("pathType", Yaml::from("Prefix")),
+                (
+                    "backend",
+                    Yaml::map(vec![(
+                        "service",
+                        Yaml::map(vec![
+                            ("name", Yaml::from(&backend.service)),
+                            ("port", service_port(node, backend, program)),
+                        ]),
+                    )]),
+                ),
+            ])
+        })
+        .collect();
+    let mut rule = Yaml::Map(vec![]);
+    if let Some(host) = &node.host {
+        rule.insert("host", Yaml::from(host));
+    }
+    rule.insert("http", Yaml::map(vec![("paths", Yaml::Seq(paths))]));
+    spec.insert("rules", Yaml::Seq(vec![rule]));
+
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("networking.k8s.io/v1")),
+        ("kind", Yaml::from("Ingress")),
+        ("metadata", metadata),
+        ("spec", spec),
+    ])
+}
+
+// backend_port returns the number of the service port a route sends requests
+// to, if the service defines it. A named port is looked up among the container
+// ports of the app the service targets, then matched to the service port that
+// forwards to that container port.
+pub fn backend_port(program: &Program, namespace: &str, backend: &IngressBackend) -> Option<i32> {
+    let service = program
+        .services
+        .iter()
+        .find(|s| s.name == backend.service && s.namespace == namespace)?;
+    match &backend.port {
+        PortRef::Number(number) => service.ports.contains_key(number).then_some(*number),
+        PortRef::Name(name) => {
+            let (_, app) = program
+                .workloads()
+                .find(|(_, d)| d.name == service.target() && d.namespace == namespace)?;
+            let container_port = app.ports.get(name)?;
+            service
+                .ports
+                .iter()
+                .find(|(_, target)| *target == container_port)
+                .map(|(port, _)| *port)
+        }
+    }
+}
+
+// service_port renders the port of a backend by number. A port that cannot be
+// resolved is written as given, for services defined outside the script.
+fn service_port(node: &IngressNode, backend: &IngressBackend, program: &Program) -> Yaml {
+    match (
+        backend_port(program, &node.namespace, backend),
+        &backend.port,
+    ) {
+        (Some(number), _) => Yaml::map(vec![("number", Yaml::from(number))]),
+        (None, PortRef::Number(number)) => Yaml::map(vec![("number", Yaml::from(*number))]),
+        (None, PortRef::Name(name)) => Yaml::map(vec![("name", Yaml::from(name))]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+    use crate::parser::parser::parse_source;
+
+    const PROGRAM: &str = "\
+deploy app web {
+    image: nginx;
+    ports { http: 8080; metrics: 9090; }
+}
+service my-svc {
+    app: web;
+    ports { port: 80; targetPort: 8080; port: 9100; targetPort: 9090; }
+}
+service admin {
+    ports { port: 80; }
+}
+ingress api {
+    host: \"api.example.com\";
+    tls: secret(api-tls);
+    routes {
+        \"/\": service(my-svc, http);
+        /admin: service(admin, 80);
+        /metrics: service(my-svc, metrics);
+    }
+}";
+
+    fn manifest(input: &str) -> String {
+        let program = parse_source(input).unwrap();
+        to_yaml_string(&ingress_manifest(&program.ingresses[0], &program))
+    }
+
+    #[test]
+    fn test_ingress_manifest() {
+        let expected = "\
+apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: api
+  namespace: default
+  labels:
+    app.kubernetes.io/managed-by: kptn
+spec:
+  tls:
+    - hosts:
+        - api.example.com
+      secretName: api-tls
+  rules:
+    - host: api.example.com
+      http:
+        paths:
+          - path: /
+            pathType: Prefix
+            backend:
+              service:
+                name: my-svc
+                port:
+                  number: 80
+          - path: /admin
+            pathType: Prefix
+            backend:
+              service:
+                name: admin
+                port:
+                  number: 80
+          - path: /metrics
+            pathType: Prefix
+            backend:
+              service:
+                name: my-svc
+                port:
+                  number: 9100
+";
+        assert_eq!(manifest(PROGRAM), expected);
+    }
+
+    #[test]
+    fn test_ingress_manifest_without_host() {
+        let input = "ingress api {\n    tls: secret(api-tls);\n    routes { /: service(external, https); }\n}";
+        let yaml = manifest(input);
+        assert!(yaml.contains("  tls:\n    - secretName: api-tls\n  rules:\n    - http:\n"));
+        assert!(yaml.contains("                port:\n                  name: https\n"));
+    }
+
+    #[test]
+    fn test_backend_port() {
+        let program = parse_source(PROGRAM).unwrap();
+        let ingress = &program.ingresses[0];
+        let ports: Vec<Option<i32>> = ingress
+            .routes
+            .values()
+            .map(|backend| backend_port(&program, "default", backend))
+            .collect();
+        assert_eq!(ports, vec![Some(80), Some(80), Some(9100)]);
+
+        let backend = |service: &str, port: &str| IngressBackend {
+            service: service.to_string(),
+            port: PortRef::parse(port),
+            span: Default::default(),
+        };
+        assert_eq!(
+            backend_port(&program, "default", &backend("my-svc", "443")),
+            None
+        );
+        assert_eq!(
+            backend_port(&program, "default", &backend("my-svc", "grpc")),
+            None
+        );
+        assert_eq!(
+            backend_port(&program, "default", &backend("admin", "http")),
+            None
+        );
+        assert_eq!(
+            backend_port(&program, "default", &backend("other", "80")),
+            None
+        );
+        assert_eq!(
+            backend_port(&program, "shop", &backend("admin", "80")),
+            None
+        );
+    }
+}
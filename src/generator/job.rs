@@ -0,0 +1,166 @@
+use crate::generator::deployment::{deployment_labels, pod_template, workload_metadata};
+use crate::generator::yaml::Yaml;
+use crate::nodes::job_node::{CronJobNode, JobNode};
+
+// job_manifest builds a `batch/v1` Job from a JobNode. The Job controller
+// generates the pod selector itself, so none is written.
+pub fn job_manifest(node: &JobNode) -> Yaml {
+    let labels = deployment_labels(&node.deployment);
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("batch/v1")),
+        ("kind", Yaml::from("Job")),
+        (
+            "metadata",
+            workload_metadata(&node.deployment, labels.clone()),
+        ),
+        ("spec", job_spec(node, labels)),
+    ])
+}
+
+// cronjob_manifest builds a `batch/v1` CronJob from a CronJobNode. The jobs it
+// creates carry the same labels as the CronJob.
+pub fn cronjob_manifest(node: &CronJobNode) -> Yaml {
+    let labels = deployment_labels(&node.job.deployment);
+    let mut spec = Yaml::map(vec![("schedule", Yaml::from(&node.schedule))]);
+    if let Some(policy) = node.concurrency_policy {
+        spec.insert("concurrencyPolicy", Yaml::from(policy.as_str()));
+    }
+    spec.insert(
+        "jobTemplate",
+        Yaml::map(vec![
+            ("metadata", Yaml::map(vec![("labels", labels.clone())])),
+            ("spec", job_spec(&node.job, labels.clone())),
+        ]),
+    );
+
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("batch/v1")),
+        ("kind", Yaml::from("CronJob")),
+        ("metadata", workload_metadata(&node.job.deployment, labels)),
+        ("spec", spec),
+    ])
+}
+
+// job_spec builds the completion settings and pod template of a job. The pod
+// template always sets its restart policy, as jobs do not accept the default.
+fn job_spec(node: &JobNode, labels: Yaml) -> Yaml {
+    let mut spec = Yaml::Map(vec![]);
+    for (key, value) in [
+        ("parallelism", node.parallelism),
+        ("completions", node.completions),
+        ("activeDeadlineSeconds", node.active_deadline_seconds),
+        ("backoffLimit", node.backoff_limit),
+    ] {
+        if let Some(value) = value {
+            spec.insert(key, Yaml::from(value));
+        }
+    }
+
+    let mut template = pod_template(&node.deployment, labels, true);
+    if let Some(pod_spec) = template.get_mut("spec") {
+        pod_spec.insert("restartPolicy", Yaml::from(node.restart_policy.as_str()));
+    }
+    spec.insert("template", template);
+    spec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+    use crate::nodes::deployment_node::DeploymentNode;
+    use crate::nodes::job_node::{ConcurrencyPolicy, RestartPolicy};
+
+    fn job() -> JobNode {
+        JobNode {
+            deployment: DeploymentNode {
+                name: "migrate".to_string(),
+                namespace: "default".to_string(),
+                replicas: 1,
+                image: "api:1.4".to_string(),
+                args: vec!["migrate".to_string()],
+                ..Default::default()
+            },
+            backoff_limit: Some(2),
+            completions: None,
+            parallelism: None,
+            active_deadline_seconds: Some(600),
+            restart_policy: RestartPolicy::OnFailure,
+        }
+    }
+
+    #[test]
+    fn test_job_manifest() {
+        let expected = "\
+apiVersion: batch/v1
+kind: Job
+metadata:
+  name: migrate
+  namespace: default
+  labels:
+    app.kubernetes.io/name: migrate
+    app.kubernetes.io/version: \"1.4\"
+    app.kubernetes.io/managed-by: kptn
+spec:
+  activeDeadlineSeconds: 600
+  backoffLimit: 2
+  template:
+    metadata:
+      labels:
+        app.kubernetes.io/name: migrate
+        app.kubernetes.io/version: \"1.4\"
+        app.kubernetes.io/managed-by: kptn
+    spec:
+      containers:
+        - name: migrate
+          image: api:1.4
+          args:
+            - migrate
+      restartPolicy: OnFailure
+";
+        assert_eq!(to_yaml_string(&job_manifest(&job())), expected);
+    }
+
+    #[test]
+    fn test_job_manifest_defaults() {
+        let mut node = job();
+        node.backoff_limit = None;
+        node.active_deadline_seconds = None;
+        node.restart_policy = RestartPolicy::default();
+        node.completions = Some(5);
+        node.parallelism = Some(2);
+        let yaml = to_yaml_string(&job_manifest(&node));
+        assert!(yaml.contains("spec:\n  parallelism: 2\n  completions: 5\n  template:\n"));
+        assert!(yaml.ends_with("      restartPolicy: Never\n"));
+        assert!(!yaml.contains("selector"));
+    }
+
+    #[test]
+    fn test_cronjob_manifest() {
+        let node = CronJobNode {
+            job: job(),
+            schedule: "0 3 * * *".to_string(),
+            concurrency_policy: Some(ConcurrencyPolicy::Forbid),
+        };
+        let yaml = to_yaml_string(&cronjob_manifest(&node));
+        assert!(yaml.starts_with("apiVersion: batch/v1\nkind: CronJob\n"));
+        assert!(yaml.contains(
+            "\
+spec:
+  schedule: 0 3 * * *
+  concurrencyPolicy: Forbid
+  jobTemplate:
+    metadata:
+      labels:
+        app.kubernetes.io/name: migrate
+        app.kubernetes.io/version: \"1.4\"
+        app.kubernetes.io/managed-by: kptn
+    spec:
+      activeDeadlineSeconds: 600
+      backoffLimit: 2
+      template:
+"
+        ));
+        assert!(yaml.ends_with("          restartPolicy: OnFailure\n"));
+    }
+}
@@ -0,0 +1,151 @@
+use crate::generator::yaml::Yaml;
+use crate::nodes::ordered_map::OrderedMap;
+use crate::validator::validator::is_label_value;
+
+// Recommended labels set on every generated object, see
+// https://kubernetes.io/docs/concepts/overview/working-with-objects/common-labels/
+pub const NAME_LABEL: &str = "app.kubernetes.io/name";
+pub const VERSION_LABEL: &str = "app.kubernetes.io/version";
+pub const MANAGED_BY_LABEL: &str = "app.kubernetes.io/managed-by";
+
+// Value of the managed-by label.
+pub const MANAGED_BY: &str = "kptn";
+
+// selector_labels returns the labels that identify the pods of an app. They only
+// hold the app name, because a Deployment's selector cannot change once created.
+pub fn selector_labels(app: &str) -> Yaml {
+    Yaml::map(vec![(NAME_LABEL, Yaml::from(app))])
+}
+
+// object_labels returns the selector labels, the version and managed-by labels,
+// then the labels written in the script. A label written in the script replaces
+// the generated one with the same key.
+pub fn object_labels(
+    app: &str,
+    version: Option<&str>,
+    labels: &OrderedMap<String, String>,
+) -> Yaml {
+    let mut out = selector_labels(app);
+    if let Some(version) = version {
+        out.insert(VERSION_LABEL, Yaml::from(version));
+    }
+    out.insert(MANAGED_BY_LABEL, Yaml::from(MANAGED_BY));
+    if let Yaml::Map(entries) = &mut out {
+        entries.retain(|(key, _)| !labels.contains_key(key));
+    }
+    for (key, value) in labels.iter() {
+        out.insert(key, Yaml::from(value));
+    }
+    out
+}
+
+// managed_labels returns the managed-by label followed by the labels written in
+// the script, for objects that do not belong to one app such as configs.
+pub fn managed_labels(labels: &OrderedMap<String, String>) -> Yaml {
+    let mut out = Yaml::Map(vec![]);
+    if !labels.contains_key(MANAGED_BY_LABEL) {
+        out.insert(MANAGED_BY_LABEL, Yaml::from(MANAGED_BY));
+    }
+    for (key, value) in labels.iter() {
+        out.insert(key, Yaml::from(value));
+    }
+    out
+}
+
+// string_map renders labels or annotations as a map.
+pub fn string_map(entries: &OrderedMap<String, String>) -> Yaml {
+    Yaml::map(
+        entries
+            .iter()
+            .map(|(key, value)| (key.clone(), Yaml::from(value)))
+            .collect(),
+    )
+}
+
+// image_version returns the tag of an image reference when it can be used as a
+// label value, e.g. `v1.0` for `ghcr.io/acme/api:v1.0`.
+pub fn image_version(image: &str) -> Option<&str> {
+    let reference = image.split('@').next().unwrap_or(image);
+    let (_, tag) = reference.rsplit_once(':')?;
+    (!tag.is_empty() && is_label_value(tag)).then_some(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+
+    #[test]
+    fn test_selector_labels() {
+        assert_eq!(
+            to_yaml_string(&selector_labels("web")),
+            "app.kubernetes.io/name: web\n"
+        );
+    }
+
+    #[test]
+    fn test_object_labels() {
+        let labels = OrderedMap::from([("tier".to_string(), "frontend".to_string())]);
+        let expected = "\
+app.kubernetes.io/name: web
+app.kubernetes.io/version: v1.2
+app.kubernetes.io/managed-by: kptn
+tier: frontend
+";
+        assert_eq!(
+            to_yaml_string(&object_labels("web", Some("v1.2"), &labels)),
+            expected
+        );
+        assert_eq!(
+            to_yaml_string(&object_labels("web", None, &OrderedMap::new())),
+            "app.kubernetes.io/name: web\napp.kubernetes.io/managed-by: kptn\n"
+        );
+    }
+
+    #[test]
+    fn test_object_labels_override_generated_ones() {
+        let labels = OrderedMap::from([(MANAGED_BY_LABEL.to_string(), "helm".to_string())]);
+        assert_eq!(
+            to_yaml_string(&object_labels("web", None, &labels)),
+            "app.kubernetes.io/name: web\napp.kubernetes.io/managed-by: helm\n"
+        );
+    }
+
+    #[test]
+    fn test_managed_labels() {
+        let labels = OrderedMap::from([("tier".to_string(), "db".to_string())]);
+        assert_eq!(
+            to_yaml_string(&managed_labels(&labels)),
+            "app.kubernetes.io/managed-by: kptn\ntier: db\n"
+        );
+        let labels = OrderedMap::from([(MANAGED_BY_LABEL.to_string(), "helm".to_string())]);
+        assert_eq!(
+            to_yaml_string(&managed_labels(&labels)),
+            "app.kubernetes.io/managed-by: helm\n"
+        );
+    }
+
+    #[test]
+    fn test_string_map() {
+        let entries = OrderedMap::from([("example.com/owner".to_string(), "team".to_string())]);
+        assert_eq!(
+            to_yaml_string(&string_map(&entries)),
+            "example.com/owner: team\n"
+        );
+        assert!(string_map(&OrderedMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_image_version() {
+        assert_eq!(image_version("my-app:v1.0"), Some("v1.0"));
+        assert_eq!(image_version("ghcr.io/acme/api:2024.1"), Some("2024.1"));
+        assert_eq!(image_version("localhost:5000/api"), None);
+        assert_eq!(image_version("localhost:5000/api:1.2"), Some("1.2"));
+        assert_eq!(image_version("api:1.2@sha256:abc"), Some("1.2"));
+        assert_eq!(image_version("api@sha256:abc"), None);
+        assert_eq!(image_version("nginx"), None);
+        assert_eq!(image_version("nginx:_x"), None);
+        assert_eq!(image_version("nginx:"), None);
+        assert_eq!(image_version(&format!("nginx:{}", "1".repeat(64))), None);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::generator::yaml::Yaml;
+
+// Namespaces that exist in every cluster and are never generated.
+const BUILTIN_NAMESPACES: [&str; 4] = ["default", "kube-system", "kube-public", "kube-node-lease"];
+
+// is_builtin_namespace reports whether a namespace is created by Kubernetes itself.
+pub fn is_builtin_namespace(name: &str) -> bool {
+    BUILTIN_NAMESPACES.contains(&name)
+}
+
+// namespace_manifest builds a `v1` Namespace.
+pub fn namespace_manifest(name: &str) -> Yaml {
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("v1")),
+        ("kind", Yaml::from("Namespace")),
+        ("metadata", Yaml::map(vec![("name", Yaml::from(name))])),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+
+    #[test]
+    fn test_namespace_manifest() {
+        assert_eq!(
+            to_yaml_string(&namespace_manifest("shop")),
+            "apiVersion: v1\nkind: Namespace\nmetadata:\n  name: shop\n"
+        );
+    }
+
+    #[test]
+    fn test_is_builtin_namespace() {
+        assert!(is_builtin_namespace("default"));
+        assert!(is_builtin_namespace("kube-system"));
+        assert!(!is_builtin_namespace("shop"));
+    }
+}
@@ -0,0 +1,251 @@
+use crate::generator::deployment::{deployment_labels, workload_selector};
+use crate::generator::labels::selector_labels;
+use crate::generator::yaml::Yaml;
+use crate::nodes::deployment_node::{DeploymentNode, PortRef};
+use crate::nodes::network_node::{Direction, NetworkNode, NetworkPeer, NetworkRule};
+use crate::parser::parser::Program;
+
+// network_policy_manifest builds a `networking.k8s.io/v1` NetworkPolicy for the
+// pods of a workload. It is named after the workload. An app peer selects the
+// pods of that workload the way the workload itself does.
+pub fn network_policy_manifest(
+    node: &DeploymentNode,
+    network: &NetworkNode,
+    program: &Program,
+) -> Yaml {
+    let policy_types = network.policy_types();
+    let mut spec = Yaml::map(vec![
+        ("podSelector", workload_selector(node)),
+        (
+            "policyTypes",
+            Yaml::Seq(
+                policy_types
+                    .iter()
+                    .map(|direction| Yaml::from(direction.as_str()))
+                    .collect(),
+            ),
+        ),
+    ]);
+    for (direction, key, peers_key) in [
+        (Direction::Ingress, "ingress", "from"),
+        (Direction::Egress, "egress", "to"),
+    ] {
+        let rules: Vec<Yaml> = network
+            .rules_for(direction)
+            .map(|rule| network_rule(node, rule, peers_key, program))
+            .collect();
+        if !rules.is_empty() {
+            spec.insert(key, Yaml::Seq(rules));
+        }
+    }
+
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("networking.k8s.io/v1")),
+        ("kind", Yaml::from("NetworkPolicy")),
+        (
+            "metadata",
+            Yaml::map(vec![
+                ("name", Yaml::from(&node.name)),
+                ("namespace", Yaml::from(&node.namespace)),
+                ("labels", deployment_labels(node)),
+            ]),
+        ),
+        ("spec", spec),
+    ])
+}
+
+// rule_port returns the number of a port a rule allows. A named port is looked
+// up in the `ports` of the pods it belongs to: the workload's own pods for
+// ingress, every app peer for egress. It is None when a name does not resolve
+// to a single number, or when the rule also sends traffic to a CIDR range.
+pub fn rule_port(
+    program: &Program,
+    node: &DeploymentNode,
+    rule: &NetworkRule,
+    port: &PortRef,
+) -> Option<i32> {
+    let name = match port {
+        PortRef::Number(number) => return Some(*number),
+        PortRef::Name(name) => name,
+    };
+    if rule.direction == Direction::Ingress {
+        return node.ports.get(name).copied();
+    }
+    let mut numbers = rule.peers.iter().map(|peer| match peer {
+        NetworkPeer::App(app) => find_app(program, &node.namespace, app)?
+            .ports
+            .get(name)
+            .copied(),
+        NetworkPeer::Cidr(_) => None,
+    });
+    let first = numbers.next()??;
+    numbers.all(|number| number == Some(first)).then_some(first)
+}
+
+// find_app returns the workload an `app(<name>)` peer refers to.
+pub fn find_app<'a>(
+    program: &'a Program,
+    namespace: &str,
+    name: &str,
+) -> Option<&'a DeploymentNode> {
+    program
+        .workloads()
+        .map(|(_, workload)| workload)
+        .find(|workload| workload.name == name && workload.namespace == namespace)
+}
+
+// network_rule renders one rule with its peers under `peers_key` and its ports.
+// A port that cannot be resolved is written by name, which Kubernetes matches
+// against the container ports of the pods at the receiving end.
+fn network_rule(
+    node: &DeploymentNode,
+    rule: &NetworkRule,
+    peers_key: &str,
+    program: &Program,
+) -> Yaml {
+    let peers = rule
+        .peers
+        .iter()
+        .map(|peer| match peer {
+            NetworkPeer::App(app) => {
+                let selector = match find_app(program, &node.namespace, app) {
+                    Some(workload) => workload_selector(workload),
+                    None => Yaml::map(vec![("matchLabels", selector_labels(app))]),
+                };
+                Yaml::map(vec![("podSelector", selector)])
+            }
+            NetworkPeer::Cidr(cidr) => Yaml::map(vec![(
+                "ipBlock",
+                Yaml::map(vec![("cidr", Yaml::from(cidr))]),
+            )]),
+        })
+        .collect();
+    let mut out = Yaml::map(vec![(peers_key, Yaml::Seq(peers))]);
+    if !rule.ports.is_empty() {
+        let ports = rule
+            .ports
+            .iter()
+            .map(|port| {
+                let port_value = match (rule_port(program, node, rule, port), port) {
+                    (Some(number), _) => Yaml::from(number),
+                    (None, PortRef::Number(number)) => Yaml::from(*number),
+                    (None, PortRef::Name(name)) => Yaml::from(name),
+                };
+                Yaml::map(vec![("protocol", Yaml::from("TCP")), ("port", port_value)])
+            })
+            .collect();
+        out.insert("ports", Yaml::Seq(ports));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+    use crate::parser::parser::parse_source;
+
+    const PROGRAM: &str = "\
+deploy app api {
+    image: api;
+    ports { http: 8080; }
+    network {
+        allow from app(frontend) on http;
+        allow to cidr(\"10.0.0.0/8\") on 5432;
+        allow to app(cache) on redis;
+        deny all egress;
+    }
+}
+deploy app frontend { image: web; }
+deploy app cache { image: redis; ports { redis: 6379; } }";
+
+    fn manifest(input: &str) -> String {
+        let program = parse_source(input).unwrap();
+        let api = &program.deployments[0];
+        to_yaml_string(&network_policy_manifest(
+            api,
+            api.network.as_ref().unwrap(),
+            &program,
+        ))
+    }
+
+    #[test]
+    fn test_network_policy_manifest() {
+        let expected = "\
+apiVersion: networking.k8s.io/v1
+kind: NetworkPolicy
+metadata:
+  name: api
+  namespace: default
+  labels:
+    app.kubernetes.io/name: api
+    app.kubernetes.io/managed-by: kptn
+spec:
+  podSelector:
+    matchLabels:
+      app.kubernetes.io/name: api
+  policyTypes:
+    - Ingress
+    - Egress
+  ingress:
+    - from:
+        - podSelector:
+            matchLabels:
+              app.kubernetes.io/name: frontend
+      ports:
+        - protocol: TCP
+          port: 8080
+  egress:
+    - to:
+        - ipBlock:
+            cidr: 10.0.0.0/8
+      ports:
+        - protocol: TCP
+          port: 5432
+    - to:
+        - podSelector:
+            matchLabels:
+              app.kubernetes.io/name: cache
+      ports:
+        - protocol: TCP
+          port: 6379
+";
+        assert_eq!(manifest(PROGRAM), expected);
+    }
+
+    #[test]
+    fn test_network_policy_deny_only() {
+        let yaml = manifest("deploy app api { network { deny all ingress; deny all egress; } }");
+        assert!(yaml.ends_with("  policyTypes:\n    - Ingress\n    - Egress\n"));
+    }
+
+    #[test]
+    fn test_rule_port() {
+        let program = parse_source(
+            "\
+deploy app api {
+    ports { http: 8080; }
+    network {
+        allow to app(a), app(b) on http;
+        allow to app(a), cidr(\"10.0.0.0/8\") on http;
+        allow to app(missing) on http, 80;
+    }
+}
+deploy app a { ports { http: 80; } }
+deploy app b { ports { http: 80; } }",
+        )
+        .unwrap();
+        let api = &program.deployments[0];
+        let rules = &api.network.as_ref().unwrap().rules;
+        let http = PortRef::Name("http".to_string());
+        assert_eq!(rule_port(&program, api, &rules[0], &http), Some(80));
+        assert_eq!(rule_port(&program, api, &rules[1], &http), None);
+        assert_eq!(rule_port(&program, api, &rules[2], &http), None);
+        assert_eq!(
+            rule_port(&program, api, &rules[2], &PortRef::Number(80)),
+            Some(80)
+        );
+        let yaml = manifest("deploy app api { network { allow to app(missing) on http; } }");
+        assert!(yaml.contains("        - protocol: TCP\n          port: http\n"));
+    }
+}
@@ -0,0 +1,304 @@
+use crate::generator::labels::{managed_labels, string_map};
+use crate::generator::yaml::Yaml;
+use crate::nodes::ordered_map::OrderedMap;
+use crate::nodes::rbac_node::{api_resource, BindingNode, RoleNode, RoleScope};
+use crate::parser::parser::{Program, DEFAULT_NAMESPACE};
+
+// API version of roles and role bindings.
+const RBAC_API_VERSION: &str = "rbac.authorization.k8s.io/v1";
+
+// Name of the service account that every namespace has, which is never generated.
+const DEFAULT_SERVICE_ACCOUNT: &str = "default";
+
+// service_account_manifest builds a `v1` ServiceAccount.
+pub fn service_account_manifest(namespace: &str, name: &str) -> Yaml {
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("v1")),
+        ("kind", Yaml::from("ServiceAccount")),
+        (
+            "metadata",
+            Yaml::map(vec![
+                ("name", Yaml::from(name)),
+                ("namespace", Yaml::from(namespace)),
+                ("labels", managed_labels(&OrderedMap::new())),
+            ]),
+        ),
+    ])
+}
+
+// role_manifest builds a Role, or a ClusterRole for a role with cluster scope.
+// Each resource becomes a rule of its own, in the API group the built-in table
+// lists for it.
+pub fn role_manifest(node: &RoleNode) -> Yaml {
+    let mut metadata = Yaml::map(vec![("name", Yaml::from(&node.name))]);
+    if node.scope == RoleScope::Namespace {
+        metadata.insert("namespace", Yaml::from(&node.namespace));
+    }
+    metadata.insert("labels", managed_labels(&node.labels));
+    if !node.annotations.is_empty() {
+        metadata.insert("annotations", string_map(&node.annotations));
+    }
+
+    let rules = node
+        .rules
+        .iter()
+        .map(|(resource, rule)| {
+            let group = api_resource(resource).map_or("", |r| r.group);
+            Yaml::map(vec![
+                ("apiGroups", Yaml::Seq(vec![Yaml::from(group)])),
+                ("resources", Yaml::Seq(vec![Yaml::from(resource)])),
+                (
+                    "verbs",
+                    Yaml::Seq(rule.verbs.iter().map(Yaml::from).collect()),
+                ),
+            ])
+        })
+        .collect();
+
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from(RBAC_API_VERSION)),
+        ("kind", Yaml::from(node.kind())),
+        ("metadata", metadata),
+        ("rules", Yaml::Seq(rules)),
+    ])
+}
+
+// binding_manifests builds a binding for every role the statement names: a
+// RoleBinding in the namespace of a Role, or a ClusterRoleBinding for a
+// ClusterRole. Each is named `<role>-<service account>`.
+pub fn binding_manifests(node: &BindingNode, program: &Program) -> Vec<Yaml> {
+    program
+        .roles
+        .iter()
+        .filter(|role| role.name == node.role)
+        .map(|role| {
+            let name = format!("{}-{}", node.role, node.service_account);
+            let (kind, metadata) = match role.scope {
+                RoleScope::Namespace => (
+                    "RoleBinding",
+                    Yaml::map(vec![
+                        ("name", Yaml::from(name)),
+                        ("namespace", Yaml::from(&role.namespace)),
+                        ("labels", managed_labels(&OrderedMap::new())),
+                    ]),
+                ),
+                RoleScope::Cluster => (
+                    "ClusterRoleBinding",
+                    Yaml::map(vec![
+                        ("name", Yaml::from(name)),
+                        ("labels", managed_labels(&OrderedMap::new())),
+                    ]),
+                ),
+            };
+            let subjects = subject_namespaces(program, role, &node.service_account)
+                .into_iter()
+                .map(|namespace| {
+                    Yaml::map(vec![
+                        ("kind", Yaml::from("ServiceAccount")),
+                        ("name", Yaml::from(&node.service_account)),
+                        ("namespace", Yaml::from(namespace)),
+                    ])
+                })
+                .collect();
+            Yaml::map(vec![
+                ("apiVersion", Yaml::from(RBAC_API_VERSION)),
+                ("kind", Yaml::from(kind)),
+                ("metadata", metadata),
+                (
+                    "roleRef",
+                    Yaml::map(vec![
+                        ("apiGroup", Yaml::from("rbac.authorization.k8s.io")),
+                        ("kind", Yaml::from(role.kind())),
+                        ("name", Yaml::from(&role.name)),
+                    ]),
+                ),
+                ("subjects", Yaml::Seq(subjects)),
+            ])
+        })
+        .collect()
+}
+
+// subject_namespaces returns the namespaces of the service accounts a role is
+// bound to. A Role binds the account in its own namespace. A ClusterRole binds
+// it in every namespace where a workload runs as it, or in the default
+// namespace when none does.
+pub fn subject_namespaces<'a>(
+    program: &'a Program,
+    role: &'a RoleNode,
+    service_account: &str,
+) -> Vec<&'a str> {
+    if role.scope == RoleScope::Namespace {
+        return vec![role.namespace.as_str()];
+    }
+    let mut namespaces = Vec::new();
+    for (_, workload) in program.workloads() {
+        if workload.service_account.as_deref() == Some(service_account)
+            && !namespaces.contains(&workload.namespace.as_str())
+        {
+            namespaces.push(workload.namespace.as_str());
+        }
+    }
+    if namespaces.is_empty() {
+        namespaces.push(DEFAULT_NAMESPACE);
+    }
+    namespaces
+}
+
+// service_accounts lists the service accounts the program uses, as
+// `(namespace, name)` pairs in order of first use: those workloads run as,
+// then those roles are bound to. The default account is left out.
+pub fn service_accounts(program: &Program) -> Vec<(&str, &str)> {
+    let workloads = program.workloads().filter_map(|(_, workload)| {
+        let name = workload.service_account.as_deref()?;
+        Some((workload.namespace.as_str(), name))
+    });
+    let bound = program.bindings.iter().flat_map(|binding| {
+        program
+            .roles
+            .iter()
+            .filter(|role| role.name == binding.role)
+            .flat_map(|role| subject_namespaces(program, role, &binding.service_account))
+            .map(|namespace| (namespace, binding.service_account.as_str()))
+    });
+    let mut accounts = Vec::new();
+    for account in workloads.chain(bound) {
+        if account.1 != DEFAULT_SERVICE_ACCOUNT && !accounts.contains(&account) {
+            accounts.push(account);
+        }
+    }
+    accounts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+    use crate::parser::parser::parse_source;
+
+    const PROGRAM: &str = "\
+deploy app web { namespace: shop; serviceAccount: web-sa; }
+deploy app worker { namespace: jobs; serviceAccount: web-sa; }
+deploy app api { serviceAccount: default; }
+role reader {
+    namespace: shop;
+    annotations { owner: team-web; }
+    rules {
+        pods: [get, list, watch];
+        deployments: [get];
+    }
+}
+role node-viewer { scope: cluster; rules { nodes: [get, list]; } }
+bind reader to web-sa;
+bind node-viewer to web-sa;
+bind node-viewer to ops;";
+
+    #[test]
+    fn test_role_manifest() {
+        let program = parse_source(PROGRAM).unwrap();
+        let expected = "\
+apiVersion: rbac.authorization.k8s.io/v1
+kind: Role
+metadata:
+  name: reader
+  namespace: shop
+  labels:
+    app.kubernetes.io/managed-by: kptn
+  annotations:
+    owner: team-web
+rules:
+  - apiGroups:
+      - \"\"
+    resources:
+      - pods
+    verbs:
+      - get
+      - list
+      - watch
+  - apiGroups:
+      - apps
+    resources:
+      - deployments
+    verbs:
+      - get
+";
+        assert_eq!(to_yaml_string(&role_manifest(&program.roles[0])), expected);
+        let cluster_role = to_yaml_string(&role_manifest(&program.roles[1]));
+        assert!(cluster_role.starts_with(
+            "apiVersion: rbac.authorization.k8s.io/v1\nkind: ClusterRole\nmetadata:\n  name: node-viewer\n  labels:\n"
+        ));
+    }
+
+    #[test]
+    fn test_binding_manifests() {
+        let program = parse_source(PROGRAM).unwrap();
+        let expected = "\
+apiVersion: rbac.authorization.k8s.io/v1
+kind: RoleBinding
+metadata:
+  name: reader-web-sa
+  namespace: shop
+  labels:
+    app.kubernetes.io/managed-by: kptn
+roleRef:
+  apiGroup: rbac.authorization.k8s.io
+  kind: Role
+  name: reader
+subjects:
+  - kind: ServiceAccount
+    name: web-sa
+    namespace: shop
+";
+        let bindings = binding_manifests(&program.bindings[0], &program);
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(to_yaml_string(&bindings[0]), expected);
+
+        let expected = "\
+apiVersion: rbac.authorization.k8s.io/v1
+kind: ClusterRoleBinding
+metadata:
+  name: node-viewer-web-sa
+  labels:
+    app.kubernetes.io/managed-by: kptn
+roleRef:
+  apiGroup: rbac.authorization.k8s.io
+  kind: ClusterRole
+  name: node-viewer
+subjects:
+  - kind: ServiceAccount
+    name: web-sa
+    namespace: shop
+  - kind: ServiceAccount
+    name: web-sa
+    namespace: jobs
+";
+        let bindings = binding_manifests(&program.bindings[1], &program);
+        assert_eq!(to_yaml_string(&bindings[0]), expected);
+
+        let unknown = BindingNode {
+            role: "writer".to_string(),
+            ..Default::default()
+        };
+        assert!(binding_manifests(&unknown, &program).is_empty());
+    }
+
+    #[test]
+    fn test_service_accounts() {
+        let program = parse_source(PROGRAM).unwrap();
+        assert_eq!(
+            service_accounts(&program),
+            vec![("shop", "web-sa"), ("jobs", "web-sa"), ("default", "ops")]
+        );
+        assert_eq!(
+            to_yaml_string(&service_account_manifest("shop", "web-sa")),
+            "\
+apiVersion: v1
+kind: ServiceAccount
+metadata:
+  name: web-sa
+  namespace: shop
+  labels:
+    app.kubernetes.io/managed-by: kptn
+"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::generator::labels::{object_labels, selector_labels, string_map};
+use crate::generator::yaml::Yaml;
+use crate::nodes::service_node::{ServiceNode, ServiceType};
+
+// service_manifest builds a `v1` Service from a ServiceNode. It selects the pods
+// of the app it targets, by default the deployment with the same name, using
+// the selector labels generated for that deployment.
+pub fn service_manifest(node: &ServiceNode) -> Yaml {
+    let selector = selector_labels(node.target());
+
+    let mut metadata = Yaml::map(vec![
+        ("name", Yaml::from(&node.name)),
+        ("namespace", Yaml::from(&node.namespace)),
+        ("labels", object_labels(node.target(), None, &node.labels)),
+    ]);
+    if !node.annotations.is_empty() {
+        metadata.insert("annotations", string_map(&node.annotations));
+    }
+
+    let mut spec = Yaml::map(vec![("type", Yaml::from(node.service_type.as_str()))]);
+    if node.service_type == ServiceType::Headless {
+        spec.insert("clusterIP", Yaml::from("None"));
+    }
+    spec.insert("selector", selector);
+    spec.insert("ports", Yaml::Seq(service_ports(node)));
+
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("v1")),
+        ("kind", Yaml::from("Service")),
+        ("metadata", metadata),
+        ("spec", spec),
+    ])
+}
+
+// service_ports renders the port mappings. Kubernetes requires every port of a
+// multi-port service to be named, so those are named after the port number.
+fn service_ports(node: &ServiceNode) -> Vec<Yaml> {
+    let named = node.ports.len() > 1;
+    node.ports
+        .iter()
+        .map(|(port, target_port)| {
+            let mut entry = Yaml::Map(vec![]);
+            if named {
+                entry.insert("name", Yaml::from(format!("port-{}", port)));
+            }
+            entry.insert("protocol", Yaml::from("TCP"));
+            entry.insert("port", Yaml::from(*port));
+            entry.insert("targetPort", Yaml::from(*target_port));
+            entry
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+    use crate::lexer::lexer::Span;
+    use crate::nodes::field_spans::FieldSpans;
+    use crate::nodes::ordered_map::OrderedMap;
+
+    #[test]
+    fn test_service_manifest() {
+        let node = ServiceNode {
+            name: "web".to_string(),
+            namespace: "shop".to_string(),
+            service_type: ServiceType::LoadBalancer,
+            ports: OrderedMap::from([(80, 8080)]),
+            labels: OrderedMap::from([("tier".to_string(), "edge".to_string())]),
+            annotations: OrderedMap::from([("example.com/owner".to_string(), "web".to_string())]),
+            app: Some("storefront".to_string()),
+            fields: FieldSpans::new(),
+            span: Span::default(),
+        };
+        let expected = "\
+apiVersion: v1
+kind: Service
+metadata:
+  name: web
+  namespace: shop
+  labels:
+    app.kubernetes.io/name: storefront
+    app.kubernetes.io/managed-by: kptn
+    tier: edge
+  annotations:
+    example.com/owner: web
+spec:
+  type: LoadBalancer
+  selector:
+    app.kubernetes.io/name: storefront
+  ports:
+    - protocol: TCP
+      port: 80
+      targetPort: 8080
+";
+        assert_eq!(to_yaml_string(&service_manifest(&node)), expected);
+    }
+
+    #[test]
+    fn test_headless_service_defaults_selector() {
+        let node = ServiceNode {
+            name: "db".to_string(),
+            namespace: "default".to_string(),
+            service_type: ServiceType::Headless,
+            ports: OrderedMap::from([(5432, 5432)]),
+            labels: OrderedMap::new(),
+            annotations: OrderedMap::new(),
+            app: None,
+            fields: FieldSpans::new(),
+            span: Span::default(),
+        };
+        let yaml = to_yaml_string(&service_manifest(&node));
+        assert!(yaml.contains("  type: ClusterIP\n  clusterIP: None\n"));
+        assert!(yaml.contains("  selector:\n    app.kubernetes.io/name: db\n"));
+        assert!(!yaml.contains("annotations"));
+    }
+
+    #[test]
+    fn test_multi_port_service_names_ports() {
+        let node = ServiceNode {
+            name: "web".to_string(),
+            namespace: "default".to_string(),
+            ports: OrderedMap::from([(80, 8080), (443, 8443)]),
+            ..Default::default()
+        };
+        let yaml = to_yaml_string(&service_manifest(&node));
+        assert!(yaml.contains("- name: port-80\n"));
+        assert!(yaml.contains("- name: port-443\n"));
+    }
+}
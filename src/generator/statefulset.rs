@@ -0,0 +1,179 @@
+use crate::generator::deployment::{
+    deployment_labels, pod_template, workload_metadata, workload_selector,
+};
+use crate::generator::labels::object_labels;
+use crate::generator::storage::claim_spec;
+use crate::generator::yaml::Yaml;
+use crate::nodes::statefulset_node::StatefulSetNode;
+
+// statefulset_manifest builds an `apps/v1` StatefulSet from a StatefulSetNode.
+// Its storage becomes a volume claim template, so every pod gets a claim of its
+// own named `<volume>-<name>-<ordinal>` instead of sharing one claim. An
+// autoscaled set leaves its replica count to the autoscaler.
+pub fn statefulset_manifest(node: &StatefulSetNode) -> Yaml {
+    let deployment = &node.deployment;
+    let labels = deployment_labels(deployment);
+
+    let mut spec = Yaml::map(vec![("serviceName", Yaml::from(&node.service_name))]);
+    if deployment.autoscale.is_none() {
+        spec.insert("replicas", Yaml::from(deployment.replicas));
+    }
+    if let Some(policy) = node.pod_management_policy {
+        spec.insert("podManagementPolicy", Yaml::from(policy.as_str()));
+    }
+    if let Some(strategy) = node.update_strategy {
+        spec.insert(
+            "updateStrategy",
+            Yaml::map(vec![("type", Yaml::from(strategy.as_str()))]),
+        );
+    }
+    spec.insert("selector", workload_selector(deployment));
+    spec.insert("template", pod_template(deployment, labels.clone(), false));
+    if let Some(storage) = &deployment.storage {
+        // Claim templates cannot change once created, so they leave out the
+        // version label that follows the image tag.
+        let claim_labels = object_labels(&deployment.name, None, &deployment.labels);
+        spec.insert(
+            "volumeClaimTemplates",
+            Yaml::Seq(vec![Yaml::map(vec![
+                (
+                    "metadata",
+                    Yaml::map(vec![
+                        ("name", Yaml::from(&storage.volume)),
+                        ("labels", claim_labels),
+                    ]),
+                ),
+                ("spec", claim_spec(storage)),
+            ])]),
+        );
+    }
+
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("apps/v1")),
+        ("kind", Yaml::from("StatefulSet")),
+        ("metadata", workload_metadata(deployment, labels)),
+        ("spec", spec),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::yaml::to_yaml_string;
+    use crate::nodes::autoscale_node::AutoscaleNode;
+    use crate::nodes::deployment_node::{DeploymentNode, StorageConfigNode};
+    use crate::nodes::ordered_map::OrderedMap;
+    use crate::nodes::quantity::Quantity;
+    use crate::nodes::statefulset_node::{PodManagementPolicy, UpdateStrategy};
+
+    fn statefulset() -> StatefulSetNode {
+        StatefulSetNode {
+            deployment: DeploymentNode {
+                name: "db".to_string(),
+                namespace: "data".to_string(),
+                replicas: 3,
+                image: "postgres:16".to_string(),
+                ports: OrderedMap::from([("sql".to_string(), 5432)]),
+                storage: Some(StorageConfigNode {
+                    volume: "pgdata".to_string(),
+                    size: Quantity::parse("10Gi").unwrap(),
+                    mount_path: "/var/lib/postgresql".to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            service_name: "db-headless".to_string(),
+            pod_management_policy: Some(PodManagementPolicy::Parallel),
+            update_strategy: Some(UpdateStrategy::OnDelete),
+        }
+    }
+
+    #[test]
+    fn test_statefulset_manifest() {
+        let expected = "\
+apiVersion: apps/v1
+kind: StatefulSet
+metadata:
+  name: db
+  namespace: data
+  labels:
+    app.kubernetes.io/name: db
+    app.kubernetes.io/version: \"16\"
+    app.kubernetes.io/managed-by: kptn
+spec:
+  serviceName: db-headless
+  replicas: 3
+  podManagementPolicy: Parallel
+  updateStrategy:
+    type: OnDelete
+  selector:
+    matchLabels:
+      app.kubernetes.io/name: db
+  template:
+    metadata:
+      labels:
+        app.kubernetes.io/name: db
+        app.kubernetes.io/version: \"16\"
+        app.kubernetes.io/managed-by: kptn
+    spec:
+      containers:
+        - name: db
+          image: postgres:16
+          ports:
+            - name: sql
+              containerPort: 5432
+          volumeMounts:
+            - name: pgdata
+              mountPath: /var/lib/postgresql
+  volumeClaimTemplates:
+    - metadata:
+        name: pgdata
+        labels:
+          app.kubernetes.io/name: db
+          app.kubernetes.io/managed-by: kptn
+      spec:
+        accessModes:
+          - ReadWriteOnce
+        resources:
+          requests:
+            storage: 10Gi
+";
+        assert_eq!(
+            to_yaml_string(&statefulset_manifest(&statefulset())),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_statefulset_manifest_omits_unset_fields() {
+        let mut node = statefulset();
+        node.deployment.storage = None;
+        node.pod_management_policy = None;
+        node.update_strategy = None;
+        let yaml = to_yaml_string(&statefulset_manifest(&node));
+        assert!(yaml.contains("spec:\n  serviceName: db-headless\n  replicas: 3\n  selector:\n"));
+        assert!(!yaml.contains("volumeClaimTemplates"));
+        assert!(!yaml.contains("volumeMounts"));
+        assert!(!yaml.contains("updateStrategy"));
+    }
+
+    #[test]
+    fn test_statefulset_manifest_autoscaled() {
+        let mut node = statefulset();
+        node.deployment.autoscale = Some(AutoscaleNode {
+            max: 5,
+            ..Default::default()
+        });
+        let yaml = to_yaml_string(&statefulset_manifest(&node));
+        assert!(
+            yaml.contains("spec:\n  serviceName: db-headless\n  podManagementPolicy: Parallel\n")
+        );
+    }
+
+    #[test]
+    fn test_statefulset_manifest_has_no_claim_volume() {
+        let yaml = to_yaml_string(&statefulset_manifest(&statefulset()));
+        assert!(!yaml.contains("persistentVolumeClaim"));
+        assert!(!yaml.contains("\n      volumes:"));
+    }
+}
@@ -0,0 +1,133 @@
+use crate::generator::yaml::Yaml;
+use crate::nodes::deployment_node::StorageConfigNode;
+
+// persistent_volume_claim builds a `v1` PersistentVolumeClaim for a storage block.
+// The claim is named after the volume and lives next to the workload using it,
+// carrying the workload's labels.
+pub fn persistent_volume_claim(storage: &StorageConfigNode, namespace: &str, labels: Yaml) -> Yaml {
+    Yaml::map(vec![
+        ("apiVersion", Yaml::from("v1")),
+        ("kind", Yaml::from("PersistentVolumeClaim")),
+        (
+            "metadata",
+            Yaml::map(vec![
+                ("name", Yaml::from(&storage.volume)),
+                ("namespace", Yaml::from(namespace)),
+                ("labels", labels),
+            ]),
+        ),
+        ("spec", claim_spec(storage)),
+    ])
+}
+
+// claim_spec builds the access modes, storage class and size request of a claim.
+pub fn claim_spec(storage: &StorageConfigNode) -> Yaml {
+    let mut spec = Yaml::map(vec![(
+        "accessModes",
+        Yaml::Seq(vec![Yaml::from(storage.access_mode.as_str())]),
+    )]);
+    if let Some(class) = &storage.storage_class {
+        spec.insert("storageClassName", Yaml::from(class));
+    }
+    spec.insert(
+        "resources",
+        Yaml::map(vec![(
+            "requests",
+            Yaml::map(vec![("storage", Yaml::from(storage.size.to_string()))]),
+        )]),
+    );
+    spec
+}
+
+// pod_volume references the claim from the pod spec.
+pub fn pod_volume(storage: &StorageConfigNode) -> Yaml {
+    Yaml::map(vec![
+        ("name", Yaml::from(&storage.volume)),
+        (
+            "persistentVolumeClaim",
+            Yaml::map(vec![("claimName", Yaml::from(&storage.volume))]),
+        ),
+    ])
+}
+
+// volume_mount mounts the claim into the container.
+pub fn volume_mount(storage: &StorageConfigNode) -> Yaml {
+    Yaml::map(vec![
+        ("name", Yaml::from(&storage.volume)),
+        ("mountPath", Yaml::from(&storage.mount_path)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::labels::selector_labels;
+    use crate::generator::yaml::to_yaml_string;
+    use crate::nodes::deployment_node::AccessMode;
+    use crate::nodes::quantity::Quantity;
+
+    fn storage() -> StorageConfigNode {
+        StorageConfigNode {
+            volume: "my-app-data".to_string(),
+            size: Quantity::parse("5Gi").unwrap(),
+            mount_path: "/var/lib/my-app".to_string(),
+            storage_class: None,
+            access_mode: AccessMode::ReadWriteOnce,
+        }
+    }
+
+    #[test]
+    fn test_persistent_volume_claim() {
+        let expected = "\
+apiVersion: v1
+kind: PersistentVolumeClaim
+metadata:
+  name: my-app-data
+  namespace: default
+  labels:
+    app.kubernetes.io/name: my-app
+spec:
+  accessModes:
+    - ReadWriteOnce
+  resources:
+    requests:
+      storage: 5Gi
+";
+        assert_eq!(
+            to_yaml_string(&persistent_volume_claim(
+                &storage(),
+                "default",
+                selector_labels("my-app")
+            )),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_persistent_volume_claim_with_class() {
+        let storage = StorageConfigNode {
+            storage_class: Some("fast-ssd".to_string()),
+            access_mode: AccessMode::ReadWriteMany,
+            ..storage()
+        };
+        let yaml = to_yaml_string(&persistent_volume_claim(
+            &storage,
+            "data",
+            Yaml::Map(vec![]),
+        ));
+        assert!(yaml.contains("  namespace: data\n"));
+        assert!(yaml.contains("    - ReadWriteMany\n  storageClassName: fast-ssd\n"));
+    }
+
+    #[test]
+    fn test_pod_volume_and_mount() {
+        assert_eq!(
+            to_yaml_string(&pod_volume(&storage())),
+            "name: my-app-data\npersistentVolumeClaim:\n  claimName: my-app-data\n"
+        );
+        assert_eq!(
+            to_yaml_string(&volume_mount(&storage())),
+            "name: my-app-data\nmountPath: /var/lib/my-app\n"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+// Contains the literals used in the autoscale DSL
+pub const AUTOSCALE_KEYWORD: &str = "autoscale";
+pub const MIN_KEYWORD: &str = "min";
+pub const MAX_KEYWORD: &str = "max";
+pub const METRICS_KEYWORD: &str = "metrics";
+pub const PODS_KEYWORD: &str = "pods";
+pub const EXTERNAL_KEYWORD: &str = "external";
+pub const BEHAVIOR_KEYWORD: &str = "behavior";
+pub const SCALE_UP_KEYWORD: &str = "scaleUp";
+pub const SCALE_DOWN_KEYWORD: &str = "scaleDown";
+pub const STABILIZATION_KEYWORD: &str = "stabilization";
+pub const PERCENT_KEYWORD: &str = "percent";
+pub const SELECT_KEYWORD: &str = "select";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(AUTOSCALE_KEYWORD, "autoscale");
+        assert_eq!(MIN_KEYWORD, "min");
+        assert_eq!(MAX_KEYWORD, "max");
+        assert_eq!(METRICS_KEYWORD, "metrics");
+        assert_eq!(PODS_KEYWORD, "pods");
+        assert_eq!(EXTERNAL_KEYWORD, "external");
+        assert_eq!(BEHAVIOR_KEYWORD, "behavior");
+        assert_eq!(SCALE_UP_KEYWORD, "scaleUp");
+        assert_eq!(SCALE_DOWN_KEYWORD, "scaleDown");
+        assert_eq!(STABILIZATION_KEYWORD, "stabilization");
+        assert_eq!(PERCENT_KEYWORD, "percent");
+        assert_eq!(SELECT_KEYWORD, "select");
+    }
+}
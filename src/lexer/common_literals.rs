@@ -2,10 +2,22 @@
 pub const SEPARATOR_VALUE: &str = "---";
 pub const RIGHT_BRACE_VALUE: &str = "}";
 pub const LEFT_BRACE_VALUE: &str = "{";
-pub const PORTS_PREFIX: &str = "ports {";
-pub const PORTS_TOKEN_VALUE: &str = "ports";
-pub const PORT_PREFIX: &str = "port:";
-pub const TARGET_PORT_PREFIX: &str = "targetPort:";
+pub const COLON_VALUE: &str = ":";
+pub const SEMICOLON_VALUE: &str = ";";
+pub const LEFT_BRACKET_VALUE: &str = "[";
+pub const RIGHT_BRACKET_VALUE: &str = "]";
+pub const COMMA_VALUE: &str = ",";
+pub const LEFT_PAREN_VALUE: &str = "(";
+pub const RIGHT_PAREN_VALUE: &str = ")";
+pub const EQUALS_VALUE: &str = "=";
+pub const DOUBLE_QUOTE_VALUE: &str = "\"";
+pub const SINGLE_QUOTE_VALUE: &str = "'";
+pub const TEXT_BLOCK_QUOTE: &str = "\"\"\"";
+pub const PORTS_KEYWORD: &str = "ports";
+pub const PORT_KEYWORD: &str = "port";
+pub const TARGET_PORT_KEYWORD: &str = "targetPort";
+pub const LABELS_KEYWORD: &str = "labels";
+pub const ANNOTATIONS_KEYWORD: &str = "annotations";
 
 // Unit tests
 #[cfg(test)]
@@ -28,22 +40,54 @@ mod tests {
     }
 
     #[test]
-    fn test_ports_prefix() {
-        assert_eq!(PORTS_PREFIX, "ports {");
+    fn test_colon_value() {
+        assert_eq!(COLON_VALUE, ":");
     }
 
     #[test]
-    fn test_ports_token_value() {
-        assert_eq!(PORTS_TOKEN_VALUE, "ports");
+    fn test_semicolon_value() {
+        assert_eq!(SEMICOLON_VALUE, ";");
     }
 
     #[test]
-    fn test_port_prefix() {
-        assert_eq!(PORT_PREFIX, "port:");
+    fn test_list_values() {
+        assert_eq!(LEFT_BRACKET_VALUE, "[");
+        assert_eq!(RIGHT_BRACKET_VALUE, "]");
+        assert_eq!(COMMA_VALUE, ",");
+        assert_eq!(LEFT_PAREN_VALUE, "(");
+        assert_eq!(RIGHT_PAREN_VALUE, ")");
+        assert_eq!(EQUALS_VALUE, "=");
     }
 
     #[test]
-    fn test_target_port_prefix() {
-        assert_eq!(TARGET_PORT_PREFIX, "targetPort:");
+    fn test_quote_values() {
+        assert_eq!(DOUBLE_QUOTE_VALUE, "\"");
+        assert_eq!(SINGLE_QUOTE_VALUE, "'");
+        assert_eq!(TEXT_BLOCK_QUOTE, "\"\"\"");
+    }
+
+    #[test]
+    fn test_ports_keyword() {
+        assert_eq!(PORTS_KEYWORD, "ports");
+    }
+
+    #[test]
+    fn test_port_keyword() {
+        assert_eq!(PORT_KEYWORD, "port");
+    }
+
+    #[test]
+    fn test_target_port_keyword() {
+        assert_eq!(TARGET_PORT_KEYWORD, "targetPort");
+    }
+
+    #[test]
+    fn test_labels_keyword() {
+        assert_eq!(LABELS_KEYWORD, "labels");
+    }
+
+    #[test]
+    fn test_annotations_keyword() {
+        assert_eq!(ANNOTATIONS_KEYWORD, "annotations");
     }
 }
@@ -0,0 +1,16 @@
+// Contains the literals used in the config and secret DSL
+pub const CONFIG_KEYWORD: &str = "config";
+pub const SECRET_KEYWORD: &str = "secret";
+pub const DATA_KEYWORD: &str = "data";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(CONFIG_KEYWORD, "config");
+        assert_eq!(SECRET_KEYWORD, "secret");
+        assert_eq!(DATA_KEYWORD, "data");
+    }
+}
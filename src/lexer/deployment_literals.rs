@@ -1,22 +1,38 @@
 // Contains the literals used in the deployment DSL
-pub const DEPLOY_APP_PREFIX: &str = "deploy app";
-pub const NAMESPACE_PREFIX: &str = "namespace:";
-pub const REPLICAS_PREFIX: &str = "replicas:";
-pub const IMAGE_PREFIX: &str = "image:";
-pub const ENV_PREFIX: &str = "env {";
-pub const ENV_TOKEN_VALUE: &str = "env";
-pub const RESOURCES_PREFIX: &str = "resources {";
-pub const RESOURCES_TOKEN_VALUE: &str = "resources";
-pub const LIMITS_PREFIX: &str = "limits {";
-pub const LIMITS_TOKEN_VALUE: &str = "limits";
-pub const REQUESTS_PREFIX: &str = "requests {";
-pub const REQUESTS_TOKEN_VALUE: &str = "requests";
-pub const MEMORY_PREFIX: &str = "memory:";
-pub const CPU_PREFIX: &str = "cpu:";
-pub const STORAGE_PREFIX: &str = "storage {";
-pub const STORAGE_TOKEN_VALUE: &str = "storage";
-pub const VOLUME_PREFIX: &str = "volume:";
-pub const SIZE_PREFIX: &str = "size:";
+pub const DEPLOY_KEYWORD: &str = "deploy";
+pub const APP_KEYWORD: &str = "app";
+pub const DEPLOY_APP_VALUE: &str = "deploy app";
+pub const NAMESPACE_KEYWORD: &str = "namespace";
+pub const REPLICAS_KEYWORD: &str = "replicas";
+pub const IMAGE_KEYWORD: &str = "image";
+pub const COMMAND_KEYWORD: &str = "command";
+pub const ARGS_KEYWORD: &str = "args";
+pub const ENV_KEYWORD: &str = "env";
+pub const RESOURCES_KEYWORD: &str = "resources";
+pub const LIMITS_KEYWORD: &str = "limits";
+pub const REQUESTS_KEYWORD: &str = "requests";
+pub const MEMORY_KEYWORD: &str = "memory";
+pub const CPU_KEYWORD: &str = "cpu";
+pub const STORAGE_KEYWORD: &str = "storage";
+pub const VOLUME_KEYWORD: &str = "volume";
+pub const SIZE_KEYWORD: &str = "size";
+pub const MOUNT_PATH_KEYWORD: &str = "mountPath";
+pub const STORAGE_CLASS_KEYWORD: &str = "storageClass";
+pub const ACCESS_MODE_KEYWORD: &str = "accessMode";
+pub const PROBES_KEYWORD: &str = "probes";
+pub const LIVENESS_KEYWORD: &str = "liveness";
+pub const READINESS_KEYWORD: &str = "readiness";
+pub const STARTUP_KEYWORD: &str = "startup";
+pub const HTTP_KEYWORD: &str = "http";
+pub const TCP_KEYWORD: &str = "tcp";
+pub const EXEC_KEYWORD: &str = "exec";
+pub const GRPC_KEYWORD: &str = "grpc";
+pub const PERIOD_KEYWORD: &str = "period";
+pub const INITIAL_DELAY_KEYWORD: &str = "initialDelay";
+pub const TIMEOUT_KEYWORD: &str = "timeout";
+pub const FAILURE_THRESHOLD_KEYWORD: &str = "failureThreshold";
+pub const SUCCESS_THRESHOLD_KEYWORD: &str = "successThreshold";
+pub const ENV_FROM_KEYWORD: &str = "envFrom";
 
 #[cfg(test)]
 mod tests {
@@ -24,23 +40,39 @@ mod tests {
 
     #[test]
     fn test_literals() {
-        assert_eq!(DEPLOY_APP_PREFIX, "deploy app");
-        assert_eq!(NAMESPACE_PREFIX, "namespace:");
-        assert_eq!(REPLICAS_PREFIX, "replicas:");
-        assert_eq!(IMAGE_PREFIX, "image:");
-        assert_eq!(ENV_PREFIX, "env {");
-        assert_eq!(ENV_TOKEN_VALUE, "env");
-        assert_eq!(RESOURCES_PREFIX, "resources {");
-        assert_eq!(RESOURCES_TOKEN_VALUE, "resources");
-        assert_eq!(LIMITS_PREFIX, "limits {");
-        assert_eq!(LIMITS_TOKEN_VALUE, "limits");
-        assert_eq!(REQUESTS_PREFIX, "requests {");
-        assert_eq!(REQUESTS_TOKEN_VALUE, "requests");
-        assert_eq!(MEMORY_PREFIX, "memory:");
-        assert_eq!(CPU_PREFIX, "cpu:");
-        assert_eq!(STORAGE_PREFIX, "storage {");
-        assert_eq!(STORAGE_TOKEN_VALUE, "storage");
-        assert_eq!(VOLUME_PREFIX, "volume:");
-        assert_eq!(SIZE_PREFIX, "size:");
+        assert_eq!(DEPLOY_KEYWORD, "deploy");
+        assert_eq!(APP_KEYWORD, "app");
+        assert_eq!(DEPLOY_APP_VALUE, "deploy app");
+        assert_eq!(NAMESPACE_KEYWORD, "namespace");
+        assert_eq!(REPLICAS_KEYWORD, "replicas");
+        assert_eq!(IMAGE_KEYWORD, "image");
+        assert_eq!(ENV_KEYWORD, "env");
+        assert_eq!(RESOURCES_KEYWORD, "resources");
+        assert_eq!(LIMITS_KEYWORD, "limits");
+        assert_eq!(REQUESTS_KEYWORD, "requests");
+        assert_eq!(MEMORY_KEYWORD, "memory");
+        assert_eq!(CPU_KEYWORD, "cpu");
+        assert_eq!(STORAGE_KEYWORD, "storage");
+        assert_eq!(VOLUME_KEYWORD, "volume");
+        assert_eq!(SIZE_KEYWORD, "size");
+        assert_eq!(MOUNT_PATH_KEYWORD, "mountPath");
+        assert_eq!(STORAGE_CLASS_KEYWORD, "storageClass");
+        assert_eq!(ACCESS_MODE_KEYWORD, "accessMode");
+        assert_eq!(COMMAND_KEYWORD, "command");
+        assert_eq!(ARGS_KEYWORD, "args");
+        assert_eq!(PROBES_KEYWORD, "probes");
+        assert_eq!(LIVENESS_KEYWORD, "liveness");
+        assert_eq!(READINESS_KEYWORD, "readiness");
+        assert_eq!(STARTUP_KEYWORD, "startup");
+        assert_eq!(HTTP_KEYWORD, "http");
+        assert_eq!(TCP_KEYWORD, "tcp");
+        assert_eq!(EXEC_KEYWORD, "exec");
+        assert_eq!(GRPC_KEYWORD, "grpc");
+        assert_eq!(PERIOD_KEYWORD, "period");
+        assert_eq!(INITIAL_DELAY_KEYWORD, "initialDelay");
+        assert_eq!(TIMEOUT_KEYWORD, "timeout");
+        assert_eq!(FAILURE_THRESHOLD_KEYWORD, "failureThreshold");
+        assert_eq!(SUCCESS_THRESHOLD_KEYWORD, "successThreshold");
+        assert_eq!(ENV_FROM_KEYWORD, "envFrom");
     }
 }
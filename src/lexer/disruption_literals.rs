@@ -0,0 +1,16 @@
+// Contains the literals used in the disruption DSL
+pub const DISRUPTION_KEYWORD: &str = "disruption";
+pub const MIN_AVAILABLE_KEYWORD: &str = "minAvailable";
+pub const MAX_UNAVAILABLE_KEYWORD: &str = "maxUnavailable";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(DISRUPTION_KEYWORD, "disruption");
+        assert_eq!(MIN_AVAILABLE_KEYWORD, "minAvailable");
+        assert_eq!(MAX_UNAVAILABLE_KEYWORD, "maxUnavailable");
+    }
+}
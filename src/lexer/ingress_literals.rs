@@ -0,0 +1,18 @@
+// Contains the literals used in the ingress DSL
+pub const INGRESS_KEYWORD: &str = "ingress";
+pub const HOST_KEYWORD: &str = "host";
+pub const TLS_KEYWORD: &str = "tls";
+pub const ROUTES_KEYWORD: &str = "routes";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(INGRESS_KEYWORD, "ingress");
+        assert_eq!(HOST_KEYWORD, "host");
+        assert_eq!(TLS_KEYWORD, "tls");
+        assert_eq!(ROUTES_KEYWORD, "routes");
+    }
+}
@@ -0,0 +1,28 @@
+// Contains the literals used in the job and cronjob DSL
+pub const JOB_KEYWORD: &str = "job";
+pub const CRONJOB_KEYWORD: &str = "cronjob";
+pub const SCHEDULE_KEYWORD: &str = "schedule";
+pub const BACKOFF_LIMIT_KEYWORD: &str = "backoffLimit";
+pub const COMPLETIONS_KEYWORD: &str = "completions";
+pub const PARALLELISM_KEYWORD: &str = "parallelism";
+pub const ACTIVE_DEADLINE_SECONDS_KEYWORD: &str = "activeDeadlineSeconds";
+pub const CONCURRENCY_POLICY_KEYWORD: &str = "concurrencyPolicy";
+pub const RESTART_POLICY_KEYWORD: &str = "restartPolicy";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(JOB_KEYWORD, "job");
+        assert_eq!(CRONJOB_KEYWORD, "cronjob");
+        assert_eq!(SCHEDULE_KEYWORD, "schedule");
+        assert_eq!(BACKOFF_LIMIT_KEYWORD, "backoffLimit");
+        assert_eq!(COMPLETIONS_KEYWORD, "completions");
+        assert_eq!(PARALLELISM_KEYWORD, "parallelism");
+        assert_eq!(ACTIVE_DEADLINE_SECONDS_KEYWORD, "activeDeadlineSeconds");
+        assert_eq!(CONCURRENCY_POLICY_KEYWORD, "concurrencyPolicy");
+        assert_eq!(RESTART_POLICY_KEYWORD, "restartPolicy");
+    }
+}
@@ -1,38 +1,107 @@
+use crate::diagnostics::codes;
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::lexer::autoscale_literals::*;
 use crate::lexer::common_literals::*;
+use crate::lexer::config_literals::*;
 use crate::lexer::deployment_literals::*;
+use crate::lexer::disruption_literals::*;
+use crate::lexer::ingress_literals::*;
+use crate::lexer::job_literals::*;
+use crate::lexer::network_literals::*;
+use crate::lexer::rbac_literals::*;
+use crate::lexer::service_literals::*;
+use crate::lexer::statefulset_literals::*;
 use crate::lexer::token::TokenType;
-use std::str::Lines;
+use crate::lexer::variable_literals::LET_KEYWORD;
 
 // LexerInterface defines the contract for any lexer implementation
 pub trait LexerInterface {
     fn next_token(&mut self) -> Token;
 }
 
-// Token represents a token with its type, value, and line number.
+// Span is the byte range a token covers in the source text.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+}
+
+// Define the TriviaKind enum for the comment styles of the DSL
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TriviaKind {
+    LineComment,  // `# ...` or `// ...`
+    BlockComment, // `/* ... */`
+}
+
+// Trivia is source text that the parser ignores but tools such as the formatter
+// keep. `text` holds the comment including its markers.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+    pub line_number: usize,
+    pub span: Span,
+}
+
+// Define the StringKind enum for the string literals of the DSL
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StringKind {
+    Quoted,    // `"..."`
+    Raw,       // `'...'`
+    TextBlock, // `"""..."""`
+}
+
+// Token represents a token with its type, value and position in the source.
+// `line_number` and `column` are 1-based; `column` counts characters.
+// `string_kind` tells which literal a string token was written as.
+//
+// Comments are attached to the nearest token: a comment that starts on the line
+// a token ends on is trailing trivia of that token, any other comment is leading
+// trivia of the token that follows it. Comments at the end of the input lead
+// the EOF token.
 #[derive(Debug, PartialEq, Clone)]
 pub struct Token {
     pub token_type: TokenType,
     pub value: String,
     pub line_number: usize,
+    pub column: usize,
+    pub span: Span,
+    pub string_kind: Option<StringKind>,
+    pub leading_trivia: Vec<Trivia>,
+    pub trailing_trivia: Vec<Trivia>,
 }
 
 // Lexer struct represents a lexer for the DSL.
 #[derive(Debug)]
 pub struct Lexer<'a> {
-    lines: Lines<'a>,
-    tokens: Vec<Token>, // Store tokens for debugging
-    pos: usize,         // Position for tracking the current token in tokens slice
-    line_number: usize, // Current line number
+    input: &'a str,
+    tokens: Vec<Token>,    // Store tokens for debugging
+    pos: usize,            // Position for tracking the current token in tokens slice
+    offset: usize,         // Byte offset of the scanner in the input
+    line_number: usize,    // Current line number
+    line_start: usize,     // Byte offset where the current line starts
+    token_end_line: usize, // Line the last scanned token ends on
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl<'a> Lexer<'a> {
     // NewLexer initializes a new Lexer and pre-tokenizes the input for debugging purposes.
     pub fn new(input: &'a str) -> Self {
         let mut lexer = Lexer {
-            lines: input.lines(),
+            input,
             tokens: Vec::new(),
             pos: 0,
+            offset: 0,
             line_number: 1,
+            line_start: 0,
+            token_end_line: 0,
+            diagnostics: Vec::new(),
         };
         lexer.tokenize(); // Pre-tokenize to store tokens without consuming.
         lexer
@@ -40,124 +109,377 @@ impl<'a> Lexer<'a> {
 
     // tokenize reads all tokens without affecting the scanning process.
     fn tokenize(&mut self) {
-        while let Some(line) = self.lines.next() {
-            let text = line.trim();
-            let token = self.scan_token(text);
-            self.tokens.push(token);
+        loop {
+            let leading_trivia = self.scan_trivia();
+            match self.scan_token() {
+                Some(mut token) => {
+                    token.leading_trivia = leading_trivia;
+                    self.tokens.push(token);
+                    self.token_end_line = self.line_number;
+                }
+                None => {
+                    let mut eof = self.eof_token();
+                    eof.leading_trivia = leading_trivia;
+                    self.tokens.push(eof); // Ensure EOF is included.
+                    break;
+                }
+            }
+        }
+    }
+
+    // diagnostics returns the problems found while scanning. Offending input is
+    // still handed to the parser as TokenIllegal.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    // scan_token skips whitespace and scans the next token, or returns None at the end.
+    fn scan_token(&mut self) -> Option<Token> {
+        self.skip_whitespace();
+        let start = self.offset;
+        let line_number = self.line_number;
+        let column = self.column(start);
+
+        let c = self.bump()?;
+        let token_type = match &self.input[start..self.offset] {
+            LEFT_BRACE_VALUE => TokenType::TokenLBrace,
+            RIGHT_BRACE_VALUE => TokenType::TokenRBrace,
+            COLON_VALUE => TokenType::TokenColon,
+            SEMICOLON_VALUE => TokenType::TokenSemicolon,
+            LEFT_BRACKET_VALUE => TokenType::TokenLBracket,
+            RIGHT_BRACKET_VALUE => TokenType::TokenRBracket,
+            COMMA_VALUE => TokenType::TokenComma,
+            LEFT_PAREN_VALUE => TokenType::TokenLParen,
+            RIGHT_PAREN_VALUE => TokenType::TokenRParen,
+            EQUALS_VALUE => TokenType::TokenEquals,
+            DOUBLE_QUOTE_VALUE if self.input[start..].starts_with(TEXT_BLOCK_QUOTE) => {
+                self.offset = start + TEXT_BLOCK_QUOTE.len();
+                return Some(self.scan_text_block(start, line_number, column));
+            }
+            DOUBLE_QUOTE_VALUE => return Some(self.scan_string(start, line_number, column)),
+            SINGLE_QUOTE_VALUE => return Some(self.scan_raw_string(start, line_number, column)),
+            _ if is_word_char(c) => self.scan_word(start),
+            _ => {
+                self.diagnostics.push(Diagnostic::error(
+                    codes::UNEXPECTED_CHARACTER,
+                    format!("unexpected character `{}`", c),
+                    Span::new(start, self.offset),
+                ));
+                TokenType::TokenIllegal
+            }
+        };
+        let value = match token_type {
+            TokenType::TokenDeployApp => DEPLOY_APP_VALUE.to_string(),
+            TokenType::TokenDeployStatefulSet => DEPLOY_STATEFULSET_VALUE.to_string(),
+            _ => self.input[start..self.offset].to_string(),
+        };
+
+        Some(self.token(token_type, value, start, line_number, column))
+    }
+
+    // scan_trivia skips whitespace and comments up to the next token. Comments on
+    // the line of the previous token become its trailing trivia; the others are
+    // returned as leading trivia of the next token.
+    fn scan_trivia(&mut self) -> Vec<Trivia> {
+        let mut leading = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = &self.input[self.offset..];
+            let kind = if rest.starts_with('#') || rest.starts_with("//") {
+                TriviaKind::LineComment
+            } else if rest.starts_with("/*") {
+                TriviaKind::BlockComment
+            } else {
+                return leading;
+            };
+
+            let start = self.offset;
+            let line_number = self.line_number;
+            match kind {
+                TriviaKind::LineComment => self.bump_while(|c| c != '\n'),
+                TriviaKind::BlockComment => self.scan_block_comment(start),
+            }
+            let trivia = Trivia {
+                kind,
+                text: self.input[start..self.offset].trim_end().to_string(),
+                line_number,
+                span: Span::new(start, self.offset),
+            };
+
+            match self.tokens.last_mut() {
+                Some(previous) if leading.is_empty() && self.token_end_line == line_number => {
+                    previous.trailing_trivia.push(trivia)
+                }
+                _ => leading.push(trivia),
+            }
+        }
+    }
+
+    // scan_block_comment consumes a `/* */` comment starting at `start`.
+    fn scan_block_comment(&mut self, start: usize) {
+        self.offset += 2;
+        match self.input[self.offset..].find("*/") {
+            Some(end) => {
+                let end = self.offset + end + 2;
+                while self.offset < end {
+                    self.bump();
+                }
+            }
+            None => {
+                self.bump_while(|_| true);
+                self.diagnostics.push(
+                    Diagnostic::error(
+                        codes::UNTERMINATED_COMMENT,
+                        "unterminated block comment",
+                        Span::new(start, start + 2),
+                    )
+                    .with_help("add `*/` to close the comment"),
+                );
+            }
+        }
+    }
+
+    // scan_word scans a keyword, number or identifier whose first character was consumed.
+    // `deploy app` and `deploy statefulset` are the only keywords spanning two words.
+    fn scan_word(&mut self, start: usize) -> TokenType {
+        self.bump_while(is_word_char);
+        let word = &self.input[start..self.offset];
+
+        if word == DEPLOY_KEYWORD {
+            let rest = &self.input[self.offset..];
+            let gap = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+            let after = &rest[gap..];
+            for (kind, token_type) in [
+                (APP_KEYWORD, TokenType::TokenDeployApp),
+                (STATEFULSET_KEYWORD, TokenType::TokenDeployStatefulSet),
+            ] {
+                if gap > 0
+                    && after.starts_with(kind)
+                    && !after[kind.len()..].starts_with(is_word_char)
+                {
+                    self.offset += gap + kind.len();
+                    return token_type;
+                }
+            }
+        }
+
+        if word == SEPARATOR_VALUE {
+            return TokenType::TokenSeparator;
+        }
+        if let Some(token_type) = keyword_token_type(word) {
+            return token_type;
+        }
+        if is_number(word) {
+            return TokenType::TokenNumber;
+        }
+        TokenType::TokenIdentifier
+    }
+
+    // scan_string scans a double-quoted string whose opening quote was consumed.
+    // Strings end at the closing quote on the same line and support the escapes
+    // `\"`, `\\`, `\n`, `\t`, `\r`, `\0` and `\u{...}`.
+    fn scan_string(&mut self, start: usize, line_number: usize, column: usize) -> Token {
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.bump();
+                    break;
+                }
+                Some('\\') => {
+                    let escape_start = self.offset;
+                    self.bump();
+                    match self.scan_escape() {
+                        Some(c) => value.push(c),
+                        None => self.diagnostics.push(
+                            Diagnostic::error(
+                                codes::INVALID_ESCAPE,
+                                format!(
+                                    "unknown escape `{}`",
+                                    &self.input[escape_start..self.offset]
+                                ),
+                                Span::new(escape_start, self.offset),
+                            )
+                            .with_help("use a single-quoted string for text with backslashes"),
+                        ),
+                    }
+                }
+                Some(c) if c != '\n' => {
+                    self.bump();
+                    value.push(c);
+                }
+                _ => return self.unterminated_string(start, line_number, column, "\""),
+            }
+        }
+        self.string_token(StringKind::Quoted, value, start, line_number, column)
+    }
+
+    // scan_escape decodes the escape sequence after a consumed backslash.
+    fn scan_escape(&mut self) -> Option<char> {
+        let c = self.peek().filter(|c| *c != '\n')?;
+        self.bump();
+        match c {
+            '"' => Some('"'),
+            '\'' => Some('\''),
+            '\\' => Some('\\'),
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            '0' => Some('\0'),
+            'u' => {
+                let rest = self.input[self.offset..].strip_prefix('{')?;
+                let digits = &rest[..rest.find('}')?];
+                if digits.is_empty()
+                    || digits.len() > 6
+                    || !digits.chars().all(|c| c.is_ascii_hexdigit())
+                {
+                    return None;
+                }
+                self.offset += digits.len() + 2;
+                char::from_u32(u32::from_str_radix(digits, 16).ok()?)
+            }
+            _ => None,
+        }
+    }
+
+    // scan_raw_string scans a single-quoted string whose opening quote was consumed.
+    // Raw strings have no escapes and end at the closing quote on the same line.
+    fn scan_raw_string(&mut self, start: usize, line_number: usize, column: usize) -> Token {
+        self.bump_while(|c| c != '\'' && c != '\n');
+        if self.peek() != Some('\'') {
+            return self.unterminated_string(start, line_number, column, "'");
+        }
+        let value = self.input[start + 1..self.offset].to_string();
+        self.bump();
+        self.string_token(StringKind::Raw, value, start, line_number, column)
+    }
+
+    // scan_text_block scans a triple-quoted string whose opening `"""` was consumed.
+    // Text blocks span lines and have no escapes; see text_block for how the
+    // value is derived from the raw text.
+    fn scan_text_block(&mut self, start: usize, line_number: usize, column: usize) -> Token {
+        let Some(length) = self.input[self.offset..].find(TEXT_BLOCK_QUOTE) else {
+            self.bump_while(|_| true);
+            self.diagnostics.push(
+                Diagnostic::error(
+                    codes::UNTERMINATED_STRING,
+                    "unterminated multi-line string",
+                    Span::new(start, start + TEXT_BLOCK_QUOTE.len()),
+                )
+                .with_help("add a closing `\"\"\"`"),
+            );
+            let value = self.input[start..].to_string();
+            return self.token(TokenType::TokenIllegal, value, start, line_number, column);
+        };
+        let end = self.offset + length + TEXT_BLOCK_QUOTE.len();
+        let value = text_block(&self.input[self.offset..self.offset + length]);
+        while self.offset < end {
+            self.bump();
+        }
+        self.string_token(StringKind::TextBlock, value, start, line_number, column)
+    }
+
+    // unterminated_string reports a quoted string that reaches the end of its line
+    // and returns an illegal token holding the unterminated literal.
+    fn unterminated_string(
+        &mut self,
+        start: usize,
+        line_number: usize,
+        column: usize,
+        quote: &str,
+    ) -> Token {
+        self.bump_while(|c| c != '\n');
+        let end = start + self.input[start..self.offset].trim_end().len();
+        self.diagnostics.push(
+            Diagnostic::error(
+                codes::UNTERMINATED_STRING,
+                "unterminated string",
+                Span::new(start, end),
+            )
+            .with_help(format!(
+                "add a closing `{}` before the end of the line",
+                quote
+            )),
+        );
+        let value = self.input[start..end].to_string();
+        self.token(TokenType::TokenIllegal, value, start, line_number, column)
+    }
+
+    fn string_token(
+        &self,
+        kind: StringKind,
+        value: String,
+        start: usize,
+        line_number: usize,
+        column: usize,
+    ) -> Token {
+        Token {
+            string_kind: Some(kind),
+            ..self.token(TokenType::TokenString, value, start, line_number, column)
+        }
+    }
+
+    // token builds a token that starts at `start` and ends at the scanner's offset.
+    fn token(
+        &self,
+        token_type: TokenType,
+        value: String,
+        start: usize,
+        line_number: usize,
+        column: usize,
+    ) -> Token {
+        Token {
+            token_type,
+            value,
+            line_number,
+            column,
+            span: Span::new(start, self.offset),
+            string_kind: None,
+            leading_trivia: Vec::new(),
+            trailing_trivia: Vec::new(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.bump_while(char::is_whitespace);
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.offset..].chars().next()
+    }
+
+    // bump consumes one character, keeping track of line boundaries.
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        if c == '\n' {
             self.line_number += 1;
+            self.line_start = self.offset;
         }
-        self.tokens.push(Token {
+        Some(c)
+    }
+
+    fn bump_while(&mut self, predicate: impl Fn(char) -> bool) {
+        while self.peek().is_some_and(&predicate) {
+            self.bump();
+        }
+    }
+
+    // column returns the 1-based character column of a byte offset on the current line.
+    fn column(&self, offset: usize) -> usize {
+        self.input[self.line_start..offset].chars().count() + 1
+    }
+
+    fn eof_token(&self) -> Token {
+        Token {
             token_type: TokenType::TokenEOF,
             value: String::new(),
             line_number: self.line_number,
-        }); // Ensure EOF is included.
-    }
-
-    // scan_token processes a single line of text into a token.
-    fn scan_token(&self, text: &str) -> Token {
-        match text {
-            _ if text == SEPARATOR_VALUE => Token {
-                token_type: TokenType::TokenSeparator,
-                value: SEPARATOR_VALUE.to_string(),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(DEPLOY_APP_PREFIX) => {
-                let value = text
-                    .trim_start_matches(DEPLOY_APP_PREFIX)
-                    .trim_end_matches(LEFT_BRACE_VALUE)
-                    .trim()
-                    .to_string();
-                Token {
-                    token_type: TokenType::TokenDeployApp,
-                    value,
-                    line_number: self.line_number,
-                }
-            }
-            _ if text.starts_with(NAMESPACE_PREFIX) => Token {
-                token_type: TokenType::TokenNamespace,
-                value: parse_string_value(text),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(REPLICAS_PREFIX) => Token {
-                token_type: TokenType::TokenReplicas,
-                value: parse_string_value(text),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(IMAGE_PREFIX) => Token {
-                token_type: TokenType::TokenImage,
-                value: parse_string_value(text),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(ENV_PREFIX) => Token {
-                token_type: TokenType::TokenEnv,
-                value: ENV_TOKEN_VALUE.to_string(),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(PORTS_PREFIX) => Token {
-                token_type: TokenType::TokenPorts,
-                value: PORTS_TOKEN_VALUE.to_string(),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(RESOURCES_PREFIX) => Token {
-                token_type: TokenType::TokenResources,
-                value: RESOURCES_TOKEN_VALUE.to_string(),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(LIMITS_PREFIX) => Token {
-                token_type: TokenType::TokenLimits,
-                value: LIMITS_TOKEN_VALUE.to_string(),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(REQUESTS_PREFIX) => Token {
-                token_type: TokenType::TokenRequests,
-                value: REQUESTS_TOKEN_VALUE.to_string(),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(MEMORY_PREFIX) => Token {
-                token_type: TokenType::TokenMemory,
-                value: parse_string_value(text),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(CPU_PREFIX) => Token {
-                token_type: TokenType::TokenCPU,
-                value: parse_string_value(text),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(STORAGE_PREFIX) => Token {
-                token_type: TokenType::TokenStorage,
-                value: STORAGE_TOKEN_VALUE.to_string(),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(VOLUME_PREFIX) => Token {
-                token_type: TokenType::TokenVolume,
-                value: parse_string_value(text),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(SIZE_PREFIX) => Token {
-                token_type: TokenType::TokenSize,
-                value: parse_string_value(text),
-                line_number: self.line_number,
-            },
-            _ if text == RIGHT_BRACE_VALUE => Token {
-                token_type: TokenType::TokenRBrace,
-                value: RIGHT_BRACE_VALUE.to_string(),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(PORT_PREFIX) => Token {
-                token_type: TokenType::TokenPort,
-                value: parse_string_value(text),
-                line_number: self.line_number,
-            },
-            _ if text.starts_with(TARGET_PORT_PREFIX) => Token {
-                token_type: TokenType::TokenTargetPort,
-                value: parse_string_value(text),
-                line_number: self.line_number,
-            },
-            _ => Token {
-                token_type: TokenType::TokenIdentifier,
-                value: text.to_string(),
-                line_number: self.line_number,
-            },
+            column: self.column(self.offset),
+            span: Span::new(self.offset, self.offset),
+            string_kind: None,
+            leading_trivia: Vec::new(),
+            trailing_trivia: Vec::new(),
         }
     }
 }
@@ -170,311 +492,695 @@ impl<'a> LexerInterface for Lexer<'a> {
             self.pos += 1;
             token
         } else {
-            Token {
-                token_type: TokenType::TokenEOF,
-                value: String::new(),
-                line_number: self.line_number,
-            }
+            self.eof_token()
         }
     }
 }
 
-// parse_string_value extracts and cleans up the string value from a line.
-fn parse_string_value(line: &str) -> String {
-    let parts: Vec<&str> = line.splitn(2, ':').collect();
-    if parts.len() < 2 {
-        return String::new();
-    }
-    let value = parts[1]
-        .trim()
-        .trim_matches(&['"', ';', '{', '}'][..])
-        .to_string();
-    value
+// keyword_token_type maps reserved words to their token type.
+fn keyword_token_type(word: &str) -> Option<TokenType> {
+    let token_type = match word {
+        NAMESPACE_KEYWORD => TokenType::TokenNamespace,
+        REPLICAS_KEYWORD => TokenType::TokenReplicas,
+        IMAGE_KEYWORD => TokenType::TokenImage,
+        COMMAND_KEYWORD => TokenType::TokenCommand,
+        ARGS_KEYWORD => TokenType::TokenArgs,
+        ENV_KEYWORD => TokenType::TokenEnv,
+        PORTS_KEYWORD => TokenType::TokenPorts,
+        RESOURCES_KEYWORD => TokenType::TokenResources,
+        LIMITS_KEYWORD => TokenType::TokenLimits,
+        REQUESTS_KEYWORD => TokenType::TokenRequests,
+        MEMORY_KEYWORD => TokenType::TokenMemory,
+        CPU_KEYWORD => TokenType::TokenCPU,
+        STORAGE_KEYWORD => TokenType::TokenStorage,
+        VOLUME_KEYWORD => TokenType::TokenVolume,
+        SIZE_KEYWORD => TokenType::TokenSize,
+        MOUNT_PATH_KEYWORD => TokenType::TokenMountPath,
+        STORAGE_CLASS_KEYWORD => TokenType::TokenStorageClass,
+        ACCESS_MODE_KEYWORD => TokenType::TokenAccessMode,
+        SERVICE_KEYWORD => TokenType::TokenService,
+        PORT_KEYWORD => TokenType::TokenPort,
+        TARGET_PORT_KEYWORD => TokenType::TokenTargetPort,
+        TYPE_KEYWORD => TokenType::TokenTypeString,
+        LABELS_KEYWORD => TokenType::TokenLabels,
+        ANNOTATIONS_KEYWORD => TokenType::TokenAnnotations,
+        APP_KEYWORD => TokenType::TokenApp,
+        PROBES_KEYWORD => TokenType::TokenProbes,
+        LIVENESS_KEYWORD => TokenType::TokenLiveness,
+        READINESS_KEYWORD => TokenType::TokenReadiness,
+        STARTUP_KEYWORD => TokenType::TokenStartup,
+        HTTP_KEYWORD => TokenType::TokenHttp,
+        TCP_KEYWORD => TokenType::TokenTcp,
+        EXEC_KEYWORD => TokenType::TokenExec,
+        GRPC_KEYWORD => TokenType::TokenGrpc,
+        PERIOD_KEYWORD => TokenType::TokenPeriod,
+        INITIAL_DELAY_KEYWORD => TokenType::TokenInitialDelay,
+        TIMEOUT_KEYWORD => TokenType::TokenTimeout,
+        FAILURE_THRESHOLD_KEYWORD => TokenType::TokenFailureThreshold,
+        SUCCESS_THRESHOLD_KEYWORD => TokenType::TokenSuccessThreshold,
+        CONFIG_KEYWORD => TokenType::TokenConfig,
+        SECRET_KEYWORD => TokenType::TokenSecret,
+        DATA_KEYWORD => TokenType::TokenData,
+        ENV_FROM_KEYWORD => TokenType::TokenEnvFrom,
+        SERVICE_NAME_KEYWORD => TokenType::TokenServiceName,
+        POD_MANAGEMENT_POLICY_KEYWORD => TokenType::TokenPodManagementPolicy,
+        UPDATE_STRATEGY_KEYWORD => TokenType::TokenUpdateStrategy,
+        JOB_KEYWORD => TokenType::TokenJob,
+        CRONJOB_KEYWORD => TokenType::TokenCronJob,
+        SCHEDULE_KEYWORD => TokenType::TokenSchedule,
+        BACKOFF_LIMIT_KEYWORD => TokenType::TokenBackoffLimit,
+        COMPLETIONS_KEYWORD => TokenType::TokenCompletions,
+        PARALLELISM_KEYWORD => TokenType::TokenParallelism,
+        ACTIVE_DEADLINE_SECONDS_KEYWORD => TokenType::TokenActiveDeadlineSeconds,
+        CONCURRENCY_POLICY_KEYWORD => TokenType::TokenConcurrencyPolicy,
+        RESTART_POLICY_KEYWORD => TokenType::TokenRestartPolicy,
+        INGRESS_KEYWORD => TokenType::TokenIngress,
+        HOST_KEYWORD => TokenType::TokenHost,
+        TLS_KEYWORD => TokenType::TokenTls,
+        ROUTES_KEYWORD => TokenType::TokenRoutes,
+        AUTOSCALE_KEYWORD => TokenType::TokenAutoscale,
+        DISRUPTION_KEYWORD => TokenType::TokenDisruption,
+        NETWORK_KEYWORD => TokenType::TokenNetwork,
+        LET_KEYWORD => TokenType::TokenLet,
+        ROLE_KEYWORD => TokenType::TokenRole,
+        MIN_KEYWORD => TokenType::TokenMin,
+        MAX_KEYWORD => TokenType::TokenMax,
+        METRICS_KEYWORD => TokenType::TokenMetrics,
+        PODS_KEYWORD => TokenType::TokenPods,
+        EXTERNAL_KEYWORD => TokenType::TokenExternal,
+        BEHAVIOR_KEYWORD => TokenType::TokenBehavior,
+        SCALE_UP_KEYWORD => TokenType::TokenScaleUp,
+        SCALE_DOWN_KEYWORD => TokenType::TokenScaleDown,
+        STABILIZATION_KEYWORD => TokenType::TokenStabilization,
+        PERCENT_KEYWORD => TokenType::TokenPercent,
+        SELECT_KEYWORD => TokenType::TokenSelect,
+        MIN_AVAILABLE_KEYWORD => TokenType::TokenMinAvailable,
+        MAX_UNAVAILABLE_KEYWORD => TokenType::TokenMaxUnavailable,
+        ALLOW_KEYWORD => TokenType::TokenAllow,
+        DENY_KEYWORD => TokenType::TokenDeny,
+        FROM_KEYWORD => TokenType::TokenFrom,
+        TO_KEYWORD => TokenType::TokenTo,
+        ON_KEYWORD => TokenType::TokenOn,
+        CIDR_KEYWORD => TokenType::TokenCidr,
+        ALL_KEYWORD => TokenType::TokenAll,
+        EGRESS_KEYWORD => TokenType::TokenEgress,
+        RULES_KEYWORD => TokenType::TokenRules,
+        SCOPE_KEYWORD => TokenType::TokenScope,
+        BIND_KEYWORD => TokenType::TokenBind,
+        SERVICE_ACCOUNT_KEYWORD => TokenType::TokenServiceAccount,
+        _ => return None,
+    };
+    Some(token_type)
+}
+
+// text_block derives the value of a triple-quoted string from its raw text. A
+// line break right after the opening quotes is dropped and the indentation
+// common to all non-blank lines is removed, so that a block can be indented
+// with the surrounding code. When the closing quotes are on a line of their
+// own the value ends with a line break, and their indentation counts too.
+fn text_block(raw: &str) -> String {
+    let raw = raw.strip_prefix('\n').unwrap_or(raw);
+    let lines: Vec<&str> = raw.split('\n').collect();
+    let closing_line = lines.last().copied().unwrap_or_default();
+    let indent_of = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| indent_of(line))
+        .chain(
+            closing_line
+                .trim()
+                .is_empty()
+                .then(|| indent_of(closing_line)),
+        )
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                ""
+            } else {
+                &line[indent..]
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// is_word_char reports whether a character can appear in an unquoted word,
+// e.g. `my-app`, `512Mi`, `0.5` or `/var/lib/data`.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | '%')
+}
+
+// is_number reports whether a word is an integer or decimal literal.
+fn is_number(word: &str) -> bool {
+    let mut parts = word.splitn(2, '.');
+    let whole = parts.next().unwrap_or_default();
+    let fraction = parts.next();
+    let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    digits(whole) && fraction.is_none_or(digits)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_scan_token() {
-        let lexer = Lexer {
-            lines: "".lines(),
-            tokens: Vec::new(),
-            pos: 0,
-            line_number: 1,
-        };
+    // types returns the token types the lexer produces for an input, without EOF.
+    fn types(input: &str) -> Vec<TokenType> {
+        let lexer = Lexer::new(input);
+        lexer
+            .tokens
+            .into_iter()
+            .map(|t| t.token_type)
+            .filter(|t| *t != TokenType::TokenEOF)
+            .collect()
+    }
 
+    fn values(input: &str) -> Vec<String> {
+        let lexer = Lexer::new(input);
+        lexer.tokens.into_iter().map(|t| t.value).collect()
+    }
+
+    #[test]
+    fn test_scan_keywords() {
         let test_cases = vec![
+            ("Separator", "---", TokenType::TokenSeparator),
+            ("DeployApp", "deploy app", TokenType::TokenDeployApp),
             (
-                "Separator",
-                "---",
-                Token {
-                    token_type: TokenType::TokenSeparator,
-                    value: SEPARATOR_VALUE.to_string(),
-                    line_number: 1,
-                },
-            ),
-            (
-                "DeployApp",
-                "deploy app myapp {",
-                Token {
-                    token_type: TokenType::TokenDeployApp,
-                    value: "myapp".to_string(),
-                    line_number: 1,
-                },
-            ),
-            (
-                "Namespace",
-                "namespace: mynamespace",
-                Token {
-                    token_type: TokenType::TokenNamespace,
-                    value: "mynamespace".to_string(),
-                    line_number: 1,
-                },
+                "DeployStatefulSet",
+                "deploy statefulset",
+                TokenType::TokenDeployStatefulSet,
             ),
+            ("Namespace", "namespace", TokenType::TokenNamespace),
+            ("Replicas", "replicas", TokenType::TokenReplicas),
+            ("Image", "image", TokenType::TokenImage),
+            ("Env", "env", TokenType::TokenEnv),
+            ("Ports", "ports", TokenType::TokenPorts),
+            ("Resources", "resources", TokenType::TokenResources),
+            ("Limits", "limits", TokenType::TokenLimits),
+            ("Requests", "requests", TokenType::TokenRequests),
+            ("Memory", "memory", TokenType::TokenMemory),
+            ("CPU", "cpu", TokenType::TokenCPU),
+            ("Storage", "storage", TokenType::TokenStorage),
+            ("Volume", "volume", TokenType::TokenVolume),
+            ("Size", "size", TokenType::TokenSize),
+            ("MountPath", "mountPath", TokenType::TokenMountPath),
+            ("StorageClass", "storageClass", TokenType::TokenStorageClass),
+            ("AccessMode", "accessMode", TokenType::TokenAccessMode),
+            ("Service", "service", TokenType::TokenService),
+            ("Labels", "labels", TokenType::TokenLabels),
+            ("Type", "type", TokenType::TokenTypeString),
+            ("Port", "port", TokenType::TokenPort),
+            ("TargetPort", "targetPort", TokenType::TokenTargetPort),
+            ("LeftBrace", "{", TokenType::TokenLBrace),
+            ("RightBrace", "}", TokenType::TokenRBrace),
+            ("Colon", ":", TokenType::TokenColon),
+            ("Semicolon", ";", TokenType::TokenSemicolon),
+            ("LeftBracket", "[", TokenType::TokenLBracket),
+            ("RightBracket", "]", TokenType::TokenRBracket),
+            ("Comma", ",", TokenType::TokenComma),
+            ("LeftParen", "(", TokenType::TokenLParen),
+            ("RightParen", ")", TokenType::TokenRParen),
+            ("Equals", "=", TokenType::TokenEquals),
+            ("Command", "command", TokenType::TokenCommand),
+            ("Args", "args", TokenType::TokenArgs),
+            ("Probes", "probes", TokenType::TokenProbes),
+            ("Liveness", "liveness", TokenType::TokenLiveness),
+            ("Readiness", "readiness", TokenType::TokenReadiness),
+            ("Startup", "startup", TokenType::TokenStartup),
+            ("Http", "http", TokenType::TokenHttp),
+            ("Tcp", "tcp", TokenType::TokenTcp),
+            ("Exec", "exec", TokenType::TokenExec),
+            ("Grpc", "grpc", TokenType::TokenGrpc),
+            ("Period", "period", TokenType::TokenPeriod),
+            ("InitialDelay", "initialDelay", TokenType::TokenInitialDelay),
+            ("Timeout", "timeout", TokenType::TokenTimeout),
             (
-                "Replicas",
-                "replicas: 3",
-                Token {
-                    token_type: TokenType::TokenReplicas,
-                    value: "3".to_string(),
-                    line_number: 1,
-                },
+                "FailureThreshold",
+                "failureThreshold",
+                TokenType::TokenFailureThreshold,
             ),
             (
-                "Image",
-                "image: myimage",
-                Token {
-                    token_type: TokenType::TokenImage,
-                    value: "myimage".to_string(),
-                    line_number: 1,
-                },
+                "SuccessThreshold",
+                "successThreshold",
+                TokenType::TokenSuccessThreshold,
             ),
+            ("Annotations", "annotations", TokenType::TokenAnnotations),
+            ("App", "app", TokenType::TokenApp),
+            ("Config", "config", TokenType::TokenConfig),
+            ("Secret", "secret", TokenType::TokenSecret),
+            ("Data", "data", TokenType::TokenData),
+            ("EnvFrom", "envFrom", TokenType::TokenEnvFrom),
+            ("ServiceName", "serviceName", TokenType::TokenServiceName),
             (
-                "Env",
-                "env {",
-                Token {
-                    token_type: TokenType::TokenEnv,
-                    value: ENV_TOKEN_VALUE.to_string(),
-                    line_number: 1,
-                },
+                "PodManagementPolicy",
+                "podManagementPolicy",
+                TokenType::TokenPodManagementPolicy,
             ),
             (
-                "Ports",
-                "ports {",
-                Token {
-                    token_type: TokenType::TokenPorts,
-                    value: PORTS_TOKEN_VALUE.to_string(),
-                    line_number: 1,
-                },
+                "UpdateStrategy",
+                "updateStrategy",
+                TokenType::TokenUpdateStrategy,
             ),
+            ("Job", "job", TokenType::TokenJob),
+            ("CronJob", "cronjob", TokenType::TokenCronJob),
+            ("Schedule", "schedule", TokenType::TokenSchedule),
+            ("BackoffLimit", "backoffLimit", TokenType::TokenBackoffLimit),
+            ("Completions", "completions", TokenType::TokenCompletions),
+            ("Parallelism", "parallelism", TokenType::TokenParallelism),
             (
-                "Resources",
-                "resources {",
-                Token {
-                    token_type: TokenType::TokenResources,
-                    value: RESOURCES_TOKEN_VALUE.to_string(),
-                    line_number: 1,
-                },
+                "ActiveDeadlineSeconds",
+                "activeDeadlineSeconds",
+                TokenType::TokenActiveDeadlineSeconds,
             ),
             (
-                "Limits",
-                "limits {",
-                Token {
-                    token_type: TokenType::TokenLimits,
-                    value: LIMITS_TOKEN_VALUE.to_string(),
-                    line_number: 1,
-                },
+                "ConcurrencyPolicy",
+                "concurrencyPolicy",
+                TokenType::TokenConcurrencyPolicy,
             ),
             (
-                "Requests",
-                "requests {",
-                Token {
-                    token_type: TokenType::TokenRequests,
-                    value: REQUESTS_TOKEN_VALUE.to_string(),
-                    line_number: 1,
-                },
+                "RestartPolicy",
+                "restartPolicy",
+                TokenType::TokenRestartPolicy,
             ),
+            ("Ingress", "ingress", TokenType::TokenIngress),
+            ("Host", "host", TokenType::TokenHost),
+            ("Tls", "tls", TokenType::TokenTls),
+            ("Routes", "routes", TokenType::TokenRoutes),
+            ("Autoscale", "autoscale", TokenType::TokenAutoscale),
+            ("Disruption", "disruption", TokenType::TokenDisruption),
+            ("Network", "network", TokenType::TokenNetwork),
+            ("Let", "let", TokenType::TokenLet),
+            ("Role", "role", TokenType::TokenRole),
+            ("Min", "min", TokenType::TokenMin),
+            ("Max", "max", TokenType::TokenMax),
+            ("Metrics", "metrics", TokenType::TokenMetrics),
+            ("Pods", "pods", TokenType::TokenPods),
+            ("External", "external", TokenType::TokenExternal),
+            ("Behavior", "behavior", TokenType::TokenBehavior),
+            ("ScaleUp", "scaleUp", TokenType::TokenScaleUp),
+            ("ScaleDown", "scaleDown", TokenType::TokenScaleDown),
             (
-                "Memory",
-                "memory: 512Mi",
-                Token {
-                    token_type: TokenType::TokenMemory,
-                    value: "512Mi".to_string(),
-                    line_number: 1,
-                },
+                "Stabilization",
+                "stabilization",
+                TokenType::TokenStabilization,
             ),
+            ("Percent", "percent", TokenType::TokenPercent),
+            ("Select", "select", TokenType::TokenSelect),
+            ("MinAvailable", "minAvailable", TokenType::TokenMinAvailable),
             (
-                "CPU",
-                "cpu: 1",
-                Token {
-                    token_type: TokenType::TokenCPU,
-                    value: "1".to_string(),
-                    line_number: 1,
-                },
+                "MaxUnavailable",
+                "maxUnavailable",
+                TokenType::TokenMaxUnavailable,
             ),
+            ("Allow", "allow", TokenType::TokenAllow),
+            ("Deny", "deny", TokenType::TokenDeny),
+            ("From", "from", TokenType::TokenFrom),
+            ("To", "to", TokenType::TokenTo),
+            ("On", "on", TokenType::TokenOn),
+            ("Cidr", "cidr", TokenType::TokenCidr),
+            ("All", "all", TokenType::TokenAll),
+            ("Egress", "egress", TokenType::TokenEgress),
+            ("Rules", "rules", TokenType::TokenRules),
+            ("Scope", "scope", TokenType::TokenScope),
+            ("Bind", "bind", TokenType::TokenBind),
             (
-                "Storage",
-                "storage {",
-                Token {
-                    token_type: TokenType::TokenStorage,
-                    value: STORAGE_TOKEN_VALUE.to_string(),
-                    line_number: 1,
-                },
-            ),
-            (
-                "Volume",
-                "volume: myvolume",
-                Token {
-                    token_type: TokenType::TokenVolume,
-                    value: "myvolume".to_string(),
-                    line_number: 1,
-                },
-            ),
-            (
-                "Size",
-                "size: 10Gi",
-                Token {
-                    token_type: TokenType::TokenSize,
-                    value: "10Gi".to_string(),
-                    line_number: 1,
-                },
-            ),
-            (
-                "RightBrace",
-                "}",
-                Token {
-                    token_type: TokenType::TokenRBrace,
-                    value: RIGHT_BRACE_VALUE.to_string(),
-                    line_number: 1,
-                },
-            ),
-            (
-                "Port",
-                "port: 8080",
-                Token {
-                    token_type: TokenType::TokenPort,
-                    value: "8080".to_string(),
-                    line_number: 1,
-                },
-            ),
-            (
-                "TargetPort",
-                "targetPort: 80",
-                Token {
-                    token_type: TokenType::TokenTargetPort,
-                    value: "80".to_string(),
-                    line_number: 1,
-                },
-            ),
-            (
-                "Identifier",
-                "someIdentifier",
-                Token {
-                    token_type: TokenType::TokenIdentifier,
-                    value: "someIdentifier".to_string(),
-                    line_number: 1,
-                },
+                "ServiceAccount",
+                "serviceAccount",
+                TokenType::TokenServiceAccount,
             ),
+            ("String", "\"value\"", TokenType::TokenString),
+            ("Number", "8080", TokenType::TokenNumber),
+            ("Decimal", "0.5", TokenType::TokenNumber),
+            ("Quantity", "512Mi", TokenType::TokenIdentifier),
+            ("Identifier", "someIdentifier", TokenType::TokenIdentifier),
+            ("Illegal", "@", TokenType::TokenIllegal),
         ];
 
-        for (name, input, expected_token) in test_cases {
-            let token = lexer.scan_token(input);
-            assert_eq!(token, expected_token, "Test case {} failed", name);
+        for (name, input, expected) in test_cases {
+            assert_eq!(types(input), vec![expected], "Test case {} failed", name);
         }
     }
 
     #[test]
-    fn test_next_token_with_tokens() {
-        let input = "token1\ntoken2";
-        let lines = input.lines();
-        let tokens = vec![
-            Token {
-                token_type: TokenType::TokenIdentifier,
-                value: "token1".to_string(),
-                line_number: 1,
-            },
-            Token {
-                token_type: TokenType::TokenIdentifier,
-                value: "token2".to_string(),
-                line_number: 2,
-            },
-        ];
-        let mut lexer = Lexer {
-            lines,
-            tokens,
-            pos: 0,
-            line_number: 1,
-        };
+    fn test_scan_statements_on_one_line() {
+        assert_eq!(
+            types("replicas: 3; image: \"x\";"),
+            vec![
+                TokenType::TokenReplicas,
+                TokenType::TokenColon,
+                TokenType::TokenNumber,
+                TokenType::TokenSemicolon,
+                TokenType::TokenImage,
+                TokenType::TokenColon,
+                TokenType::TokenString,
+                TokenType::TokenSemicolon,
+            ]
+        );
+    }
 
-        let token1 = lexer.next_token();
-        assert_eq!(token1.token_type, TokenType::TokenIdentifier);
-        assert_eq!(token1.value, "token1");
+    #[test]
+    fn test_scan_block_on_one_line() {
+        assert_eq!(
+            types("deploy app a { namespace: \"n\"; }"),
+            vec![
+                TokenType::TokenDeployApp,
+                TokenType::TokenIdentifier,
+                TokenType::TokenLBrace,
+                TokenType::TokenNamespace,
+                TokenType::TokenColon,
+                TokenType::TokenString,
+                TokenType::TokenSemicolon,
+                TokenType::TokenRBrace,
+            ]
+        );
+        assert_eq!(
+            values("deploy app a { namespace: \"n\"; }"),
+            vec!["deploy app", "a", "{", "namespace", ":", "n", ";", "}", ""]
+        );
+    }
 
-        let token2 = lexer.next_token();
-        assert_eq!(token2.token_type, TokenType::TokenIdentifier);
-        assert_eq!(token2.value, "token2");
+    #[test]
+    fn test_scan_list_literal() {
+        assert_eq!(
+            types("args: [\"--port\", 8080,--verbose];"),
+            vec![
+                TokenType::TokenArgs,
+                TokenType::TokenColon,
+                TokenType::TokenLBracket,
+                TokenType::TokenString,
+                TokenType::TokenComma,
+                TokenType::TokenNumber,
+                TokenType::TokenComma,
+                TokenType::TokenIdentifier,
+                TokenType::TokenRBracket,
+                TokenType::TokenSemicolon,
+            ]
+        );
+    }
 
-        let eof_token = lexer.next_token();
-        assert_eq!(eof_token.token_type, TokenType::TokenEOF);
+    #[test]
+    fn test_scan_reference() {
+        assert_eq!(
+            values("DB_PASS: secret(db-creds,password);"),
+            vec!["DB_PASS", ":", "secret", "(", "db-creds", ",", "password", ")", ";", ""]
+        );
+        assert_eq!(
+            types("envFrom: config(app-config)")[2..],
+            [
+                TokenType::TokenConfig,
+                TokenType::TokenLParen,
+                TokenType::TokenIdentifier,
+                TokenType::TokenRParen,
+            ]
+        );
     }
 
     #[test]
-    fn test_next_token_no_tokens() {
-        let input = "";
-        let lines = input.lines();
-        let tokens = vec![];
-        let mut lexer = Lexer {
-            lines,
-            tokens,
-            pos: 0,
-            line_number: 1,
-        };
+    fn test_scan_deploy_without_app() {
+        assert_eq!(
+            types("deploy application"),
+            vec![TokenType::TokenIdentifier, TokenType::TokenIdentifier]
+        );
+        assert_eq!(
+            types("deploy\napp"),
+            vec![TokenType::TokenIdentifier, TokenType::TokenApp]
+        );
+        assert_eq!(values("deploy \t app x"), vec!["deploy app", "x", ""]);
+        assert_eq!(
+            values("deploy  statefulset db"),
+            vec!["deploy statefulset", "db", ""]
+        );
+        assert_eq!(
+            types("deploy statefulsets"),
+            vec![TokenType::TokenIdentifier, TokenType::TokenIdentifier]
+        );
+    }
 
-        let eof_token = lexer.next_token();
-        assert_eq!(eof_token.token_type, TokenType::TokenEOF);
+    #[test]
+    fn test_scan_string_keeps_delimiters() {
+        assert_eq!(
+            values("url: \"postgres://u:p@h/db;{x}\";"),
+            vec!["url", ":", "postgres://u:p@h/db;{x}", ";", ""]
+        );
+    }
+
+    #[test]
+    fn test_scan_string_escapes() {
+        assert_eq!(
+            values(r#"a: "say \"hi\"\n\tto \\ \u{1F600} \u{e9}";"#),
+            vec!["a", ":", "say \"hi\"\n\tto \\ \u{1F600} \u{e9}", ";", ""]
+        );
+        assert!(Lexer::new(r#"a: "\r\0\'";"#).diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_scan_invalid_escapes() {
+        for (input, escape) in [
+            (r#""\q""#, "\\q"),
+            (r#""\u{110000}""#, "\\u{110000}"),
+            (r#""\u{12345678}""#, "\\u"),
+            (r#""\u{zz}""#, "\\u"),
+        ] {
+            let lexer = Lexer::new(input);
+            let diagnostics = lexer.diagnostics();
+            assert_eq!(diagnostics.len(), 1, "lexing {}", input);
+            assert_eq!(diagnostics[0].code, codes::INVALID_ESCAPE);
+            assert_eq!(
+                diagnostics[0].message,
+                format!("unknown escape `{}`", escape)
+            );
+        }
+    }
+
+    #[test]
+    fn test_scan_raw_string() {
+        let lexer = Lexer::new(r"path: 'C:\data\n';");
+        assert_eq!(lexer.tokens[2].token_type, TokenType::TokenString);
+        assert_eq!(lexer.tokens[2].value, r"C:\data\n");
+        assert_eq!(lexer.tokens[2].string_kind, Some(StringKind::Raw));
+        assert!(lexer.diagnostics().is_empty());
+
+        let lexer = Lexer::new("path: 'open\n");
+        assert_eq!(lexer.tokens[2].token_type, TokenType::TokenIllegal);
+        assert_eq!(
+            lexer.diagnostics()[0].help.as_deref(),
+            Some("add a closing `'` before the end of the line")
+        );
+    }
+
+    #[test]
+    fn test_scan_text_block() {
+        let input =
+            "data: \"\"\"\n    server {\n        listen 80;\n    }\n    \"\"\"; # nginx\nimage: x;";
+        let lexer = Lexer::new(input);
+        let token = &lexer.tokens[2];
+        assert_eq!(token.token_type, TokenType::TokenString);
+        assert_eq!(token.value, "server {\n    listen 80;\n}\n");
+        assert_eq!(token.string_kind, Some(StringKind::TextBlock));
+        assert_eq!(token.line_number, 1);
+        assert_eq!(&input[token.span.end - 3..token.span.end], "\"\"\"");
+        assert_eq!(lexer.tokens[3].trailing_trivia[0].text, "# nginx");
+        assert_eq!(lexer.tokens[4].line_number, 6);
+    }
+
+    #[test]
+    fn test_text_block() {
+        assert_eq!(text_block("inline"), "inline");
+        assert_eq!(text_block("\n  a\n\n    b\n  "), "a\n\n  b\n");
+        assert_eq!(text_block("\n    a\n    b"), "a\nb");
+        assert_eq!(text_block("\n    a\n  "), "  a\n");
+        assert_eq!(text_block(""), "");
+    }
+
+    #[test]
+    fn test_scan_unterminated_text_block() {
+        let lexer = Lexer::new("data: \"\"\"\nnever closed\n");
+        assert_eq!(lexer.tokens[2].token_type, TokenType::TokenIllegal);
+        let diagnostics = lexer.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "unterminated multi-line string");
+        assert_eq!(diagnostics[0].span, Span::new(6, 9));
+    }
+
+    #[test]
+    fn test_scan_unterminated_string() {
+        let lexer = Lexer::new("image: \"nginx\nreplicas: 1");
+        let token = &lexer.tokens[2];
+        assert_eq!(token.token_type, TokenType::TokenIllegal);
+        assert_eq!(token.value, "\"nginx");
+        assert_eq!(lexer.tokens[3].token_type, TokenType::TokenReplicas);
+        assert_eq!(lexer.tokens[3].line_number, 2);
+
+        let diagnostics = lexer.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, codes::UNTERMINATED_STRING);
+        assert_eq!(diagnostics[0].span, Span::new(7, 13));
+    }
+
+    #[test]
+    fn test_unexpected_character_diagnostic() {
+        let lexer = Lexer::new("replicas: 3 @");
+        let diagnostics = lexer.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, codes::UNEXPECTED_CHARACTER);
+        assert_eq!(diagnostics[0].message, "unexpected character `@`");
+        assert_eq!(diagnostics[0].span, Span::new(12, 13));
+        assert!(Lexer::new("deploy app a { }").diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_scan_comments_are_not_tokens() {
+        let input =
+            "# scale for black friday\nreplicas: 3; # peak\n// TODO\nimage: /* inline */ nginx;";
+        assert_eq!(
+            types(input),
+            vec![
+                TokenType::TokenReplicas,
+                TokenType::TokenColon,
+                TokenType::TokenNumber,
+                TokenType::TokenSemicolon,
+                TokenType::TokenImage,
+                TokenType::TokenColon,
+                TokenType::TokenIdentifier,
+                TokenType::TokenSemicolon,
+            ]
+        );
+        assert!(Lexer::new(input).diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_comments_attach_to_nearest_token() {
+        let input = "# leading\n// second\nreplicas: 3; # peak\nimage: nginx; /* a */ /* b */\n/* end\n   of file */";
+        let tokens = Lexer::new(input).tokens;
+        let texts = |trivia: &[Trivia]| trivia.iter().map(|t| t.text.clone()).collect::<Vec<_>>();
+
+        assert_eq!(
+            texts(&tokens[0].leading_trivia),
+            vec!["# leading", "// second"]
+        );
+        assert_eq!(tokens[0].leading_trivia[0].kind, TriviaKind::LineComment);
+        assert_eq!(tokens[0].leading_trivia[1].line_number, 2);
+        assert_eq!(texts(&tokens[3].trailing_trivia), vec!["# peak"]);
+        assert!(tokens[4].leading_trivia.is_empty());
+        assert_eq!(
+            texts(&tokens[7].trailing_trivia),
+            vec!["/* a */", "/* b */"]
+        );
+
+        let eof = tokens.last().unwrap();
+        assert_eq!(eof.token_type, TokenType::TokenEOF);
+        assert_eq!(texts(&eof.leading_trivia), vec!["/* end\n   of file */"]);
+        assert_eq!(eof.leading_trivia[0].kind, TriviaKind::BlockComment);
+        assert_eq!(eof.line_number, 6);
     }
 
     #[test]
-    fn test_parse_string_value_with_colon() {
-        let input = "key: value";
-        let expected = "value".to_string();
-        assert_eq!(parse_string_value(input), expected);
+    fn test_comment_markers_inside_values() {
+        assert_eq!(
+            values("mountPath: /var/lib//data; image: \"a#b\";"),
+            vec![
+                "mountPath",
+                ":",
+                "/var/lib//data",
+                ";",
+                "image",
+                ":",
+                "a#b",
+                ";",
+                ""
+            ]
+        );
+        assert_eq!(values("size: 1Gi#c"), vec!["size", ":", "1Gi", ""]);
     }
 
     #[test]
-    fn test_parse_string_value_with_quotes() {
-        let input = "key: \"value\"";
-        let expected = "value".to_string();
-        assert_eq!(parse_string_value(input), expected);
+    fn test_unterminated_block_comment() {
+        let lexer = Lexer::new("replicas: 3;\n/* never closed\nimage: x;");
+        let diagnostics = lexer.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, codes::UNTERMINATED_COMMENT);
+        assert_eq!(diagnostics[0].span, Span::new(13, 15));
+        assert_eq!(lexer.tokens.len(), 5);
+    }
+
+    #[test]
+    fn test_token_positions() {
+        let input = "deploy app web {\n    replicas: 3;\n}";
+        let lexer = Lexer::new(input);
+        let replicas = &lexer.tokens[3];
+        assert_eq!(replicas.token_type, TokenType::TokenReplicas);
+        assert_eq!(replicas.line_number, 2);
+        assert_eq!(replicas.column, 5);
+        assert_eq!(replicas.span, Span::new(21, 29));
+        assert_eq!(&input[replicas.span.start..replicas.span.end], "replicas");
+
+        let number = &lexer.tokens[5];
+        assert_eq!(number.column, 15);
+        assert_eq!(&input[number.span.start..number.span.end], "3");
+
+        let eof = lexer.tokens.last().unwrap();
+        assert_eq!(eof.token_type, TokenType::TokenEOF);
+        assert_eq!(eof.line_number, 3);
+        assert_eq!(eof.span, Span::new(input.len(), input.len()));
     }
 
     #[test]
-    fn test_parse_string_value_with_braces() {
-        let input = "key: {value}";
-        let expected = "value".to_string();
-        assert_eq!(parse_string_value(input), expected);
+    fn test_string_span_includes_quotes() {
+        let input = "image: \"nginx\"";
+        let lexer = Lexer::new(input);
+        let token = &lexer.tokens[2];
+        assert_eq!(token.value, "nginx");
+        assert_eq!(&input[token.span.start..token.span.end], "\"nginx\"");
+        assert_eq!(token.string_kind, Some(StringKind::Quoted));
+        assert_eq!(lexer.tokens[0].string_kind, None);
     }
 
     #[test]
-    fn test_parse_string_value_with_semicolon() {
-        let input = "key: value;";
-        let expected = "value".to_string();
-        assert_eq!(parse_string_value(input), expected);
+    fn test_column_counts_characters() {
+        let lexer = Lexer::new("\"é\" x");
+        assert_eq!(lexer.tokens[1].column, 5);
+        assert_eq!(lexer.tokens[1].span, Span::new(5, 6));
     }
 
     #[test]
-    fn test_parse_string_value_no_colon() {
-        let input = "key value";
-        let expected = "".to_string();
-        assert_eq!(parse_string_value(input), expected);
+    fn test_next_token_with_tokens() {
+        let mut lexer = Lexer::new("token1\ntoken2");
+
+        let token1 = lexer.next_token();
+        assert_eq!(token1.token_type, TokenType::TokenIdentifier);
+        assert_eq!(token1.value, "token1");
+
+        let token2 = lexer.next_token();
+        assert_eq!(token2.token_type, TokenType::TokenIdentifier);
+        assert_eq!(token2.value, "token2");
+        assert_eq!(token2.line_number, 2);
+
+        let eof_token = lexer.next_token();
+        assert_eq!(eof_token.token_type, TokenType::TokenEOF);
+
+        let past_eof = lexer.next_token();
+        assert_eq!(past_eof.token_type, TokenType::TokenEOF);
+    }
+
+    #[test]
+    fn test_next_token_no_tokens() {
+        let mut lexer = Lexer::new("");
+
+        let eof_token = lexer.next_token();
+        assert_eq!(eof_token.token_type, TokenType::TokenEOF);
+        assert_eq!(eof_token.line_number, 1);
     }
 
     #[test]
-    fn test_parse_string_value_empty_string() {
-        let input = "";
-        let expected = "".to_string();
-        assert_eq!(parse_string_value(input), expected);
+    fn test_is_number() {
+        assert!(is_number("3"));
+        assert!(is_number("0.25"));
+        assert!(!is_number("1."));
+        assert!(!is_number(".5"));
+        assert!(!is_number("1.2.3"));
+        assert!(!is_number("500m"));
     }
 }
@@ -0,0 +1,28 @@
+// Contains the literals used in the network DSL
+pub const NETWORK_KEYWORD: &str = "network";
+pub const ALLOW_KEYWORD: &str = "allow";
+pub const DENY_KEYWORD: &str = "deny";
+pub const FROM_KEYWORD: &str = "from";
+pub const TO_KEYWORD: &str = "to";
+pub const ON_KEYWORD: &str = "on";
+pub const CIDR_KEYWORD: &str = "cidr";
+pub const ALL_KEYWORD: &str = "all";
+pub const EGRESS_KEYWORD: &str = "egress";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(NETWORK_KEYWORD, "network");
+        assert_eq!(ALLOW_KEYWORD, "allow");
+        assert_eq!(DENY_KEYWORD, "deny");
+        assert_eq!(FROM_KEYWORD, "from");
+        assert_eq!(TO_KEYWORD, "to");
+        assert_eq!(ON_KEYWORD, "on");
+        assert_eq!(CIDR_KEYWORD, "cidr");
+        assert_eq!(ALL_KEYWORD, "all");
+        assert_eq!(EGRESS_KEYWORD, "egress");
+    }
+}
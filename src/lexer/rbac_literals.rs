@@ -0,0 +1,20 @@
+// Contains the literals used in the RBAC DSL
+pub const ROLE_KEYWORD: &str = "role";
+pub const RULES_KEYWORD: &str = "rules";
+pub const SCOPE_KEYWORD: &str = "scope";
+pub const BIND_KEYWORD: &str = "bind";
+pub const SERVICE_ACCOUNT_KEYWORD: &str = "serviceAccount";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(ROLE_KEYWORD, "role");
+        assert_eq!(RULES_KEYWORD, "rules");
+        assert_eq!(SCOPE_KEYWORD, "scope");
+        assert_eq!(BIND_KEYWORD, "bind");
+        assert_eq!(SERVICE_ACCOUNT_KEYWORD, "serviceAccount");
+    }
+}
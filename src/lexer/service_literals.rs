@@ -0,0 +1,14 @@
+// Contains the literals used in the service DSL
+pub const SERVICE_KEYWORD: &str = "service";
+pub const TYPE_KEYWORD: &str = "type";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(SERVICE_KEYWORD, "service");
+        assert_eq!(TYPE_KEYWORD, "type");
+    }
+}
@@ -0,0 +1,20 @@
+// Contains the literals used in the statefulset DSL
+pub const STATEFULSET_KEYWORD: &str = "statefulset";
+pub const DEPLOY_STATEFULSET_VALUE: &str = "deploy statefulset";
+pub const SERVICE_NAME_KEYWORD: &str = "serviceName";
+pub const POD_MANAGEMENT_POLICY_KEYWORD: &str = "podManagementPolicy";
+pub const UPDATE_STRATEGY_KEYWORD: &str = "updateStrategy";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(STATEFULSET_KEYWORD, "statefulset");
+        assert_eq!(DEPLOY_STATEFULSET_VALUE, "deploy statefulset");
+        assert_eq!(SERVICE_NAME_KEYWORD, "serviceName");
+        assert_eq!(POD_MANAGEMENT_POLICY_KEYWORD, "podManagementPolicy");
+        assert_eq!(UPDATE_STRATEGY_KEYWORD, "updateStrategy");
+    }
+}
@@ -1,4 +1,5 @@
 // TokenType represents the different types of tokens in the DSL.
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, PartialEq, Clone)]
 pub enum TokenType {
     TokenEOF,
@@ -28,6 +29,85 @@ pub enum TokenType {
     TokenTargetPort,
     TokenTypeString,
     TokenSeparator, // ---
+    TokenMountPath,
+    TokenStorageClass,
+    TokenAccessMode,
+    TokenLabels,
+    TokenSemicolon, // ;
+    TokenIllegal,   // a character or literal the lexer does not understand
+    TokenLBracket,  // [
+    TokenRBracket,  // ]
+    TokenComma,     // ,
+    TokenCommand,
+    TokenProbes,
+    TokenLiveness,
+    TokenReadiness,
+    TokenStartup,
+    TokenHttp,
+    TokenTcp,
+    TokenExec,
+    TokenGrpc,
+    TokenPeriod,
+    TokenInitialDelay,
+    TokenTimeout,
+    TokenFailureThreshold,
+    TokenSuccessThreshold,
+    TokenAnnotations,
+    TokenApp,
+    TokenConfig,
+    TokenSecret,
+    TokenData,
+    TokenEnvFrom,
+    TokenLParen,            // (
+    TokenRParen,            // )
+    TokenDeployStatefulSet, // deploy statefulset
+    TokenServiceName,
+    TokenPodManagementPolicy,
+    TokenUpdateStrategy,
+    TokenJob,
+    TokenCronJob,
+    TokenSchedule,
+    TokenBackoffLimit,
+    TokenCompletions,
+    TokenParallelism,
+    TokenActiveDeadlineSeconds,
+    TokenConcurrencyPolicy,
+    TokenRestartPolicy,
+    TokenIngress,
+    TokenHost,
+    TokenTls,
+    TokenRoutes,
+    TokenAutoscale,
+    TokenMin,
+    TokenMax,
+    TokenMetrics,
+    TokenPods,
+    TokenExternal,
+    TokenBehavior,
+    TokenScaleUp,
+    TokenScaleDown,
+    TokenStabilization,
+    TokenPercent,
+    TokenSelect,
+    TokenDisruption,
+    TokenMinAvailable,
+    TokenMaxUnavailable,
+    TokenNetwork,
+    TokenAllow,
+    TokenDeny,
+    TokenFrom,
+    TokenTo,
+    TokenOn,
+    TokenCidr,
+    TokenAll,
+    TokenEgress,
+    TokenRole,
+    TokenRules,
+    TokenScope,
+    TokenBind,
+    TokenServiceAccount,
+    TokenEquals, // =
+    TokenLet,
 }
 
 // Unit tests
@@ -196,4 +276,478 @@ mod tests {
         let token = TokenType::TokenSeparator;
         assert_eq!(token, TokenType::TokenSeparator);
     }
+
+    #[test]
+    fn test_token_mount_path() {
+        let token = TokenType::TokenMountPath;
+        assert_eq!(token, TokenType::TokenMountPath);
+    }
+
+    #[test]
+    fn test_token_storage_class() {
+        let token = TokenType::TokenStorageClass;
+        assert_eq!(token, TokenType::TokenStorageClass);
+    }
+
+    #[test]
+    fn test_token_access_mode() {
+        let token = TokenType::TokenAccessMode;
+        assert_eq!(token, TokenType::TokenAccessMode);
+    }
+
+    #[test]
+    fn test_token_labels() {
+        let token = TokenType::TokenLabels;
+        assert_eq!(token, TokenType::TokenLabels);
+    }
+
+    #[test]
+    fn test_token_semicolon() {
+        let token = TokenType::TokenSemicolon;
+        assert_eq!(token, TokenType::TokenSemicolon);
+    }
+
+    #[test]
+    fn test_token_illegal() {
+        let token = TokenType::TokenIllegal;
+        assert_eq!(token, TokenType::TokenIllegal);
+    }
+
+    #[test]
+    fn test_token_l_bracket() {
+        let token = TokenType::TokenLBracket;
+        assert_eq!(token, TokenType::TokenLBracket);
+    }
+
+    #[test]
+    fn test_token_r_bracket() {
+        let token = TokenType::TokenRBracket;
+        assert_eq!(token, TokenType::TokenRBracket);
+    }
+
+    #[test]
+    fn test_token_comma() {
+        let token = TokenType::TokenComma;
+        assert_eq!(token, TokenType::TokenComma);
+    }
+
+    #[test]
+    fn test_token_command() {
+        let token = TokenType::TokenCommand;
+        assert_eq!(token, TokenType::TokenCommand);
+    }
+
+    #[test]
+    fn test_token_probes() {
+        let token = TokenType::TokenProbes;
+        assert_eq!(token, TokenType::TokenProbes);
+    }
+
+    #[test]
+    fn test_token_liveness() {
+        let token = TokenType::TokenLiveness;
+        assert_eq!(token, TokenType::TokenLiveness);
+    }
+
+    #[test]
+    fn test_token_readiness() {
+        let token = TokenType::TokenReadiness;
+        assert_eq!(token, TokenType::TokenReadiness);
+    }
+
+    #[test]
+    fn test_token_startup() {
+        let token = TokenType::TokenStartup;
+        assert_eq!(token, TokenType::TokenStartup);
+    }
+
+    #[test]
+    fn test_token_http() {
+        let token = TokenType::TokenHttp;
+        assert_eq!(token, TokenType::TokenHttp);
+    }
+
+    #[test]
+    fn test_token_tcp() {
+        let token = TokenType::TokenTcp;
+        assert_eq!(token, TokenType::TokenTcp);
+    }
+
+    #[test]
+    fn test_token_exec() {
+        let token = TokenType::TokenExec;
+        assert_eq!(token, TokenType::TokenExec);
+    }
+
+    #[test]
+    fn test_token_grpc() {
+        let token = TokenType::TokenGrpc;
+        assert_eq!(token, TokenType::TokenGrpc);
+    }
+
+    #[test]
+    fn test_token_period() {
+        let token = TokenType::TokenPeriod;
+        assert_eq!(token, TokenType::TokenPeriod);
+    }
+
+    #[test]
+    fn test_token_initial_delay() {
+        let token = TokenType::TokenInitialDelay;
+        assert_eq!(token, TokenType::TokenInitialDelay);
+    }
+
+    #[test]
+    fn test_token_timeout() {
+        let token = TokenType::TokenTimeout;
+        assert_eq!(token, TokenType::TokenTimeout);
+    }
+
+    #[test]
+    fn test_token_failure_threshold() {
+        let token = TokenType::TokenFailureThreshold;
+        assert_eq!(token, TokenType::TokenFailureThreshold);
+    }
+
+    #[test]
+    fn test_token_success_threshold() {
+        let token = TokenType::TokenSuccessThreshold;
+        assert_eq!(token, TokenType::TokenSuccessThreshold);
+    }
+
+    #[test]
+    fn test_token_annotations() {
+        let token = TokenType::TokenAnnotations;
+        assert_eq!(token, TokenType::TokenAnnotations);
+    }
+
+    #[test]
+    fn test_token_app() {
+        let token = TokenType::TokenApp;
+        assert_eq!(token, TokenType::TokenApp);
+    }
+
+    #[test]
+    fn test_token_config() {
+        let token = TokenType::TokenConfig;
+        assert_eq!(token, TokenType::TokenConfig);
+    }
+
+    #[test]
+    fn test_token_secret() {
+        let token = TokenType::TokenSecret;
+        assert_eq!(token, TokenType::TokenSecret);
+    }
+
+    #[test]
+    fn test_token_data() {
+        let token = TokenType::TokenData;
+        assert_eq!(token, TokenType::TokenData);
+    }
+
+    #[test]
+    fn test_token_env_from() {
+        let token = TokenType::TokenEnvFrom;
+        assert_eq!(token, TokenType::TokenEnvFrom);
+    }
+
+    #[test]
+    fn test_token_l_paren() {
+        let token = TokenType::TokenLParen;
+        assert_eq!(token, TokenType::TokenLParen);
+    }
+
+    #[test]
+    fn test_token_r_paren() {
+        let token = TokenType::TokenRParen;
+        assert_eq!(token, TokenType::TokenRParen);
+    }
+
+    #[test]
+    fn test_token_deploy_stateful_set() {
+        let token = TokenType::TokenDeployStatefulSet;
+        assert_eq!(token, TokenType::TokenDeployStatefulSet);
+    }
+
+    #[test]
+    fn test_token_service_name() {
+        let token = TokenType::TokenServiceName;
+        assert_eq!(token, TokenType::TokenServiceName);
+    }
+
+    #[test]
+    fn test_token_pod_management_policy() {
+        let token = TokenType::TokenPodManagementPolicy;
+        assert_eq!(token, TokenType::TokenPodManagementPolicy);
+    }
+
+    #[test]
+    fn test_token_update_strategy() {
+        let token = TokenType::TokenUpdateStrategy;
+        assert_eq!(token, TokenType::TokenUpdateStrategy);
+    }
+
+    #[test]
+    fn test_token_job() {
+        let token = TokenType::TokenJob;
+        assert_eq!(token, TokenType::TokenJob);
+    }
+
+    #[test]
+    fn test_token_cron_job() {
+        let token = TokenType::TokenCronJob;
+        assert_eq!(token, TokenType::TokenCronJob);
+    }
+
+    #[test]
+    fn test_token_schedule() {
+        let token = TokenType::TokenSchedule;
+        assert_eq!(token, TokenType::TokenSchedule);
+    }
+
+    #[test]
+    fn test_token_backoff_limit() {
+        let token = TokenType::TokenBackoffLimit;
+        assert_eq!(token, TokenType::TokenBackoffLimit);
+    }
+
+    #[test]
+    fn test_token_completions() {
+        let token = TokenType::TokenCompletions;
+        assert_eq!(token, TokenType::TokenCompletions);
+    }
+
+    #[test]
+    fn test_token_parallelism() {
+        let token = TokenType::TokenParallelism;
+        assert_eq!(token, TokenType::TokenParallelism);
+    }
+
+    #[test]
+    fn test_token_active_deadline_seconds() {
+        let token = TokenType::TokenActiveDeadlineSeconds;
+        assert_eq!(token, TokenType::TokenActiveDeadlineSeconds);
+    }
+
+    #[test]
+    fn test_token_concurrency_policy() {
+        let token = TokenType::TokenConcurrencyPolicy;
+        assert_eq!(token, TokenType::TokenConcurrencyPolicy);
+    }
+
+    #[test]
+    fn test_token_restart_policy() {
+        let token = TokenType::TokenRestartPolicy;
+        assert_eq!(token, TokenType::TokenRestartPolicy);
+    }
+
+    #[test]
+    fn test_token_ingress() {
+        let token = TokenType::TokenIngress;
+        assert_eq!(token, TokenType::TokenIngress);
+    }
+
+    #[test]
+    fn test_token_host() {
+        let token = TokenType::TokenHost;
+        assert_eq!(token, TokenType::TokenHost);
+    }
+
+    #[test]
+    fn test_token_tls() {
+        let token = TokenType::TokenTls;
+        assert_eq!(token, TokenType::TokenTls);
+    }
+
+    #[test]
+    fn test_token_routes() {
+        let token = TokenType::TokenRoutes;
+        assert_eq!(token, TokenType::TokenRoutes);
+    }
+
+    #[test]
+    fn test_token_autoscale() {
+        let token = TokenType::TokenAutoscale;
+        assert_eq!(token, TokenType::TokenAutoscale);
+    }
+
+    #[test]
+    fn test_token_min() {
+        let token = TokenType::TokenMin;
+        assert_eq!(token, TokenType::TokenMin);
+    }
+
+    #[test]
+    fn test_token_max() {
+        let token = TokenType::TokenMax;
+        assert_eq!(token, TokenType::TokenMax);
+    }
+
+    #[test]
+    fn test_token_metrics() {
+        let token = TokenType::TokenMetrics;
+        assert_eq!(token, TokenType::TokenMetrics);
+    }
+
+    #[test]
+    fn test_token_pods() {
+        let token = TokenType::TokenPods;
+        assert_eq!(token, TokenType::TokenPods);
+    }
+
+    #[test]
+    fn test_token_external() {
+        let token = TokenType::TokenExternal;
+        assert_eq!(token, TokenType::TokenExternal);
+    }
+
+    #[test]
+    fn test_token_behavior() {
+        let token = TokenType::TokenBehavior;
+        assert_eq!(token, TokenType::TokenBehavior);
+    }
+
+    #[test]
+    fn test_token_scale_up() {
+        let token = TokenType::TokenScaleUp;
+        assert_eq!(token, TokenType::TokenScaleUp);
+    }
+
+    #[test]
+    fn test_token_scale_down() {
+        let token = TokenType::TokenScaleDown;
+        assert_eq!(token, TokenType::TokenScaleDown);
+    }
+
+    #[test]
+    fn test_token_stabilization() {
+        let token = TokenType::TokenStabilization;
+        assert_eq!(token, TokenType::TokenStabilization);
+    }
+
+    #[test]
+    fn test_token_percent() {
+        let token = TokenType::TokenPercent;
+        assert_eq!(token, TokenType::TokenPercent);
+    }
+
+    #[test]
+    fn test_token_select() {
+        let token = TokenType::TokenSelect;
+        assert_eq!(token, TokenType::TokenSelect);
+    }
+
+    #[test]
+    fn test_token_disruption() {
+        let token = TokenType::TokenDisruption;
+        assert_eq!(token, TokenType::TokenDisruption);
+    }
+
+    #[test]
+    fn test_token_min_available() {
+        let token = TokenType::TokenMinAvailable;
+        assert_eq!(token, TokenType::TokenMinAvailable);
+    }
+
+    #[test]
+    fn test_token_max_unavailable() {
+        let token = TokenType::TokenMaxUnavailable;
+        assert_eq!(token, TokenType::TokenMaxUnavailable);
+    }
+
+    #[test]
+    fn test_token_network() {
+        let token = TokenType::TokenNetwork;
+        assert_eq!(token, TokenType::TokenNetwork);
+    }
+
+    #[test]
+    fn test_token_allow() {
+        let token = TokenType::TokenAllow;
+        assert_eq!(token, TokenType::TokenAllow);
+    }
+
+    #[test]
+    fn test_token_deny() {
+        let token = TokenType::TokenDeny;
+        assert_eq!(token, TokenType::TokenDeny);
+    }
+
+    #[test]
+    fn test_token_from() {
+        let token = TokenType::TokenFrom;
+        assert_eq!(token, TokenType::TokenFrom);
+    }
+
+    #[test]
+    fn test_token_to() {
+        let token = TokenType::TokenTo;
+        assert_eq!(token, TokenType::TokenTo);
+    }
+
+    #[test]
+    fn test_token_on() {
+        let token = TokenType::TokenOn;
+        assert_eq!(token, TokenType::TokenOn);
+    }
+
+    #[test]
+    fn test_token_cidr() {
+        let token = TokenType::TokenCidr;
+        assert_eq!(token, TokenType::TokenCidr);
+    }
+
+    #[test]
+    fn test_token_all() {
+        let token = TokenType::TokenAll;
+        assert_eq!(token, TokenType::TokenAll);
+    }
+
+    #[test]
+    fn test_token_egress() {
+        let token = TokenType::TokenEgress;
+        assert_eq!(token, TokenType::TokenEgress);
+    }
+
+    #[test]
+    fn test_token_role() {
+        let token = TokenType::TokenRole;
+        assert_eq!(token, TokenType::TokenRole);
+    }
+
+    #[test]
+    fn test_token_rules() {
+        let token = TokenType::TokenRules;
+        assert_eq!(token, TokenType::TokenRules);
+    }
+
+    #[test]
+    fn test_token_scope() {
+        let token = TokenType::TokenScope;
+        assert_eq!(token, TokenType::TokenScope);
+    }
+
+    #[test]
+    fn test_token_bind() {
+        let token = TokenType::TokenBind;
+        assert_eq!(token, TokenType::TokenBind);
+    }
+
+    #[test]
+    fn test_token_service_account() {
+        let token = TokenType::TokenServiceAccount;
+        assert_eq!(token, TokenType::TokenServiceAccount);
+    }
+
+    #[test]
+    fn test_token_equals() {
+        let token = TokenType::TokenEquals;
+        assert_eq!(token, TokenType::TokenEquals);
+    }
+
+    #[test]
+    fn test_token_let() {
+        let token = TokenType::TokenLet;
+        assert_eq!(token, TokenType::TokenLet);
+    }
 }
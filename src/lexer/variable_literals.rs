@@ -0,0 +1,18 @@
+// Contains the literals used for variables in the DSL
+pub const LET_KEYWORD: &str = "let";
+pub const INTERPOLATION_START: &str = "${";
+pub const INTERPOLATION_END: &str = "}";
+pub const INTERPOLATION_ESCAPE: &str = "$${";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(LET_KEYWORD, "let");
+        assert_eq!(INTERPOLATION_START, "${");
+        assert_eq!(INTERPOLATION_END, "}");
+        assert_eq!(INTERPOLATION_ESCAPE, "$${");
+    }
+}
@@ -1,18 +1,88 @@
+// Modules are named after their directory (e.g. `lexer::lexer`)
+#![allow(clippy::module_inception)]
+
 // Declare the nodes module and its submodules
 pub mod nodes {
+    pub mod autoscale_node;
+    pub mod config_node;
+    pub mod cron;
     pub mod deployment_node;
+    pub mod disruption_node;
+    pub mod field_spans;
+    pub mod ingress_node;
+    pub mod job_node;
+    pub mod network_node;
     pub mod node;
+    pub mod ordered_map;
+    pub mod quantity;
+    pub mod rbac_node;
+    pub mod secret_node;
     pub mod service_node;
+    pub mod statefulset_node;
 }
 
 // Declare the lexer module
 pub mod lexer {
+    mod autoscale_literals;
     mod common_literals;
+    mod config_literals;
     mod deployment_literals;
+    mod disruption_literals;
+    mod ingress_literals;
+    mod job_literals;
     pub mod lexer;
-    mod token;
+    mod network_literals;
+    mod rbac_literals;
+    mod service_literals;
+    mod statefulset_literals;
+    pub mod token;
+    pub mod variable_literals;
+}
+
+// Declare the parser module
+pub mod parser {
+    pub mod parser;
+    pub mod variables;
+}
+
+// Declare the diagnostics module
+pub mod diagnostics {
+    pub mod codes;
+    pub mod diagnostic;
+    pub mod render;
+}
+
+// Declare the validator module
+pub mod validator {
+    pub mod validator;
+}
+
+// Declare the generator module
+pub mod generator {
+    pub mod autoscale;
+    pub mod config;
+    pub mod deployment;
+    pub mod disruption;
+    pub mod generator;
+    pub mod ingress;
+    pub mod job;
+    pub mod labels;
+    pub mod namespace;
+    pub mod network;
+    pub mod rbac;
+    pub mod service;
+    pub mod statefulset;
+    pub mod storage;
+    pub mod yaml;
+}
+
+// Declare the formatter module
+pub mod formatter {
+    pub mod formatter;
 }
 
 pub mod cmd {
+    pub mod check;
+    pub mod fmt;
     pub mod generate;
 }
@@ -1,14 +1,18 @@
 use clap::Command;
-use neon::cmd::generate;
+use neon::cmd::{check, fmt, generate};
 
 fn main() {
     let matches = Command::new("kptn")
         .about("kptn is a CLI for managing Kubernetes resources using the Krypton DSL")
         .subcommand(generate::new_generate_command())
+        .subcommand(check::new_check_command())
+        .subcommand(fmt::new_fmt_command())
         .get_matches();
 
     match matches.subcommand() {
         Some(("generate", sub_m)) => generate::execute_generate_command(sub_m),
+        Some(("check", sub_m)) => check::execute_check_command(sub_m),
+        Some(("fmt", sub_m)) => fmt::execute_fmt_command(sub_m),
         _ => eprintln!("Unknown command"),
     }
 }
@@ -0,0 +1,149 @@
+use crate::lexer::lexer::Span;
+use crate::nodes::ordered_map::OrderedMap;
+use crate::nodes::quantity::Quantity;
+
+// Define the ResourceTarget enum for scaling on cpu or memory: a percentage of
+// the requested amount, or an average amount per pod
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResourceTarget {
+    Utilization(i32),
+    AverageValue(Quantity),
+}
+
+// Define the MetricSource enum for where a custom metric comes from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MetricSource {
+    Pods,     // A metric reported by every pod, averaged across them
+    External, // A metric of something outside the cluster, such as a queue
+}
+
+impl MetricSource {
+    // as_str returns the HPA metric type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricSource::Pods => "Pods",
+            MetricSource::External => "External",
+        }
+    }
+}
+
+// Define the CustomMetric struct, a metric to hold at an average value per pod
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomMetric {
+    pub source: MetricSource,
+    pub target: Quantity,
+}
+
+// Define the SelectPolicy enum for picking among the policies of a direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelectPolicy {
+    Max,
+    Min,
+    Disabled, // Never scale in this direction
+}
+
+impl SelectPolicy {
+    // parse accepts the Kubernetes policy names, ignoring case.
+    pub fn parse(value: &str) -> Option<SelectPolicy> {
+        match value.to_ascii_lowercase().as_str() {
+            "max" => Some(SelectPolicy::Max),
+            "min" => Some(SelectPolicy::Min),
+            "disabled" => Some(SelectPolicy::Disabled),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SelectPolicy::Max => "Max",
+            SelectPolicy::Min => "Min",
+            SelectPolicy::Disabled => "Disabled",
+        }
+    }
+}
+
+// Define the ScalingPolicy struct: change by at most `value` pods or percent
+// of the current pods every `period` seconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScalingPolicy {
+    pub value: i32,
+    pub period: i32,
+}
+
+// Define the ScalingRulesNode struct for the `scaleUp` or `scaleDown` behavior
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ScalingRulesNode {
+    pub stabilization: Option<i32>, // Window in seconds
+    pub pods: Option<ScalingPolicy>,
+    pub percent: Option<ScalingPolicy>,
+    pub select: Option<SelectPolicy>,
+}
+
+// Define the AutoscaleNode struct. A workload with autoscaling leaves its
+// replica count to the HorizontalPodAutoscaler.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AutoscaleNode {
+    pub min: Option<i32>,
+    pub max: i32,
+    pub cpu: Option<ResourceTarget>,
+    pub memory: Option<ResourceTarget>,
+    pub metrics: OrderedMap<String, CustomMetric>, // Map of `metric name,metric`
+    pub scale_up: Option<ScalingRulesNode>,
+    pub scale_down: Option<ScalingRulesNode>,
+    pub span: Span, // Location of the autoscale keyword in the source
+}
+
+impl AutoscaleNode {
+    // resources yields the cpu and memory targets that are set, with the name
+    // of their resource.
+    pub fn resources(&self) -> impl Iterator<Item = (&'static str, &ResourceTarget)> {
+        [("cpu", &self.cpu), ("memory", &self.memory)]
+            .into_iter()
+            .filter_map(|(name, target)| target.as_ref().map(|target| (name, target)))
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resources() {
+        let autoscale = AutoscaleNode {
+            max: 10,
+            memory: Some(ResourceTarget::Utilization(80)),
+            ..Default::default()
+        };
+        let resources: Vec<(&str, &ResourceTarget)> = autoscale.resources().collect();
+        assert_eq!(
+            resources,
+            vec![("memory", &ResourceTarget::Utilization(80))]
+        );
+        let autoscale = AutoscaleNode {
+            cpu: Some(ResourceTarget::AverageValue(
+                Quantity::parse("500m").unwrap(),
+            )),
+            ..autoscale
+        };
+        let names: Vec<&str> = autoscale.resources().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["cpu", "memory"]);
+    }
+
+    #[test]
+    fn test_select_policy_parse() {
+        assert_eq!(SelectPolicy::parse("Max"), Some(SelectPolicy::Max));
+        assert_eq!(
+            SelectPolicy::parse("disabled"),
+            Some(SelectPolicy::Disabled)
+        );
+        assert_eq!(SelectPolicy::parse("average"), None);
+        assert_eq!(SelectPolicy::Min.as_str(), "Min");
+    }
+
+    #[test]
+    fn test_metric_source() {
+        assert_eq!(MetricSource::Pods.as_str(), "Pods");
+        assert_eq!(MetricSource::External.as_str(), "External");
+    }
+}
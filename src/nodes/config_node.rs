@@ -0,0 +1,46 @@
+use crate::lexer::lexer::Span;
+use crate::nodes::field_spans::FieldSpans;
+use crate::nodes::node::Node;
+use crate::nodes::ordered_map::OrderedMap;
+
+// Define the ConfigNode struct
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ConfigNode {
+    pub name: String,
+    pub namespace: String,
+    pub labels: OrderedMap<String, String>,
+    pub annotations: OrderedMap<String, String>,
+    pub data: OrderedMap<String, String>,
+    pub fields: FieldSpans, // Where the fields of the block are written
+    pub span: Span,         // Location of the name in the source
+}
+
+// Implement the Node trait for ConfigNode
+impl Node for ConfigNode {
+    fn node_type(&self) -> String {
+        "ConfigMap".to_string()
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_node_creation() {
+        let config_node = ConfigNode {
+            name: "app-config".to_string(),
+            namespace: "default".to_string(),
+            labels: OrderedMap::new(),
+            annotations: OrderedMap::new(),
+            data: OrderedMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]),
+            fields: FieldSpans::new(),
+            span: Span::default(),
+        };
+
+        assert_eq!(config_node.name, "app-config");
+        assert_eq!(config_node.data.get("LOG_LEVEL").unwrap(), "debug");
+        assert_eq!(config_node.node_type(), "ConfigMap");
+    }
+}
@@ -0,0 +1,180 @@
+// Fields of a cron schedule in order, with their range and the names they
+// accept in place of numbers. The first name stands for the lowest value.
+const CRON_FIELDS: [(&str, u32, u32, &[&str]); 5] = [
+    ("minute", 0, 59, &[]),
+    ("hour", 0, 23, &[]),
+    ("day of month", 1, 31, &[]),
+    (
+        "month",
+        1,
+        12,
+        &[
+            "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+        ],
+    ),
+    (
+        "day of week",
+        0,
+        6,
+        &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"],
+    ),
+];
+
+// Shorthands that the CronJob controller accepts instead of the five fields.
+const CRON_DESCRIPTORS: [&str; 7] = [
+    "@yearly",
+    "@annually",
+    "@monthly",
+    "@weekly",
+    "@daily",
+    "@midnight",
+    "@hourly",
+];
+
+// check_schedule reports why a CronJob schedule is not valid, following the
+// syntax of the CronJob controller: five fields (minute, hour, day of month,
+// month, day of week) made of comma-separated values, `a-b` ranges and `*`,
+// each with an optional `/step`, or one of the `@daily` style shorthands.
+pub fn check_schedule(schedule: &str) -> Result<(), String> {
+    let schedule = schedule.trim();
+    if schedule.starts_with('@') {
+        return match CRON_DESCRIPTORS.contains(&schedule) {
+            true => Ok(()),
+            false => Err(format!("unknown shorthand `{}`", schedule)),
+        };
+    }
+    let fields: Vec<&str> = schedule.split_whitespace().collect();
+    if fields.len() != CRON_FIELDS.len() {
+        return Err(format!(
+            "expected {} fields, found {}",
+            CRON_FIELDS.len(),
+            fields.len()
+        ));
+    }
+    for (value, field) in fields.into_iter().zip(CRON_FIELDS) {
+        for part in value.split(',') {
+            if part.is_empty() {
+                return Err(format!("empty value in {} `{}`", field.0, value));
+            }
+            check_part(part, field)?;
+        }
+    }
+    Ok(())
+}
+
+// check_part checks one comma-separated part of a field, such as `1-5/2`.
+fn check_part(
+    part: &str,
+    (field, min, max, names): (&str, u32, u32, &[&str]),
+) -> Result<(), String> {
+    let (range, step) = match part.split_once('/') {
+        Some((range, step)) => (range, Some(step)),
+        None => (part, None),
+    };
+    if let Some(step) = step {
+        match step.parse::<u32>() {
+            Ok(step) if step >= 1 => {}
+            _ => return Err(format!("invalid step in {} `{}`", field, part)),
+        }
+    }
+    if range == "*" || range == "?" {
+        return Ok(());
+    }
+
+    let value = |text: &str| -> Result<u32, String> {
+        let number = match names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(text))
+        {
+            Some(index) => min + index as u32,
+            None => text
+                .parse::<u32>()
+                .map_err(|_| format!("invalid {} `{}`", field, part))?,
+        };
+        if number < min || number > max {
+            return Err(format!(
+                "{} `{}` is out of range {}-{}",
+                field, text, min, max
+            ));
+        }
+        Ok(number)
+    };
+    match range.split_once('-') {
+        Some((start, end)) => {
+            if value(start)? > value(end)? {
+                return Err(format!("{} range `{}` is reversed", field, range));
+            }
+        }
+        None => {
+            value(range)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_schedules() {
+        for schedule in [
+            "0 3 * * *",
+            "*/15 * * * *",
+            "0 0-23/2 * * *",
+            "30 9 1,15 * MON-FRI",
+            "0 0 ? jan,jul sun",
+            "5 4 * * 0",
+            "0 12 31 12 6",
+            "15/5 * * * *",
+            "  0 3 * * *  ",
+            "@daily",
+            "@hourly",
+        ] {
+            assert_eq!(check_schedule(schedule), Ok(()), "{}", schedule);
+        }
+    }
+
+    #[test]
+    fn test_field_count() {
+        assert_eq!(
+            check_schedule("0 3 * *"),
+            Err("expected 5 fields, found 4".to_string())
+        );
+        assert_eq!(
+            check_schedule("0 0 3 * * *"),
+            Err("expected 5 fields, found 6".to_string())
+        );
+        assert_eq!(
+            check_schedule(""),
+            Err("expected 5 fields, found 0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let cases = [
+            ("60 * * * *", "minute `60` is out of range 0-59"),
+            ("0 24 * * *", "hour `24` is out of range 0-23"),
+            ("0 0 0 * *", "day of month `0` is out of range 1-31"),
+            ("0 0 1 13 *", "month `13` is out of range 1-12"),
+            ("0 0 * * 7", "day of week `7` is out of range 0-6"),
+            ("0 0 * * FUN", "invalid day of week `FUN`"),
+            ("0 0 * JAN-MARCH *", "invalid month `JAN-MARCH`"),
+            ("a * * * *", "invalid minute `a`"),
+            ("*/0 * * * *", "invalid step in minute `*/0`"),
+            ("*/x * * * *", "invalid step in minute `*/x`"),
+            ("0 10-2 * * *", "hour range `10-2` is reversed"),
+            ("0 1,,2 * * *", "empty value in hour `1,,2`"),
+            ("@every 5m", "unknown shorthand `@every 5m`"),
+        ];
+        for (schedule, reason) in cases {
+            assert_eq!(
+                check_schedule(schedule),
+                Err(reason.to_string()),
+                "{}",
+                schedule
+            );
+        }
+    }
+}
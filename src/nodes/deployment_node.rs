@@ -1,35 +1,184 @@
+use crate::lexer::lexer::Span;
+use crate::nodes::autoscale_node::AutoscaleNode;
+use crate::nodes::disruption_node::DisruptionNode;
+use crate::nodes::field_spans::FieldSpans;
+use crate::nodes::network_node::NetworkNode;
 use crate::nodes::node::Node;
-use std::collections::HashMap;
+use crate::nodes::ordered_map::OrderedMap;
+use crate::nodes::quantity::Quantity;
 
 // Define the ResourceSpec struct
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct ResourceSpec {
-    pub memory: String,
-    pub cpu: String,
+    pub memory: Option<Quantity>,
+    pub cpu: Option<Quantity>,
 }
 
 // Define the ResourceRequirementsNode struct
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct ResourceRequirementsNode {
     pub limits: ResourceSpec,
     pub requests: ResourceSpec,
 }
 
+// Define the AccessMode enum for persistent volume claims
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum AccessMode {
+    #[default]
+    ReadWriteOnce,
+    ReadOnlyMany,
+    ReadWriteMany,
+    ReadWriteOncePod,
+}
+
+impl AccessMode {
+    // parse accepts the Kubernetes name or its kubectl abbreviation (RWO, ROX, RWX, RWOP).
+    pub fn parse(value: &str) -> Option<AccessMode> {
+        match value {
+            "ReadWriteOnce" | "RWO" => Some(AccessMode::ReadWriteOnce),
+            "ReadOnlyMany" | "ROX" => Some(AccessMode::ReadOnlyMany),
+            "ReadWriteMany" | "RWX" => Some(AccessMode::ReadWriteMany),
+            "ReadWriteOncePod" | "RWOP" => Some(AccessMode::ReadWriteOncePod),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessMode::ReadWriteOnce => "ReadWriteOnce",
+            AccessMode::ReadOnlyMany => "ReadOnlyMany",
+            AccessMode::ReadWriteMany => "ReadWriteMany",
+            AccessMode::ReadWriteOncePod => "ReadWriteOncePod",
+        }
+    }
+}
+
 // Define the StorageConfigNode struct
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct StorageConfigNode {
     pub volume: String,
-    pub size: String,
+    pub size: Quantity,
+    pub mount_path: String,
+    pub storage_class: Option<String>,
+    pub access_mode: AccessMode,
+}
+
+// Define the PortRef enum for a container port given by number or by name
+#[derive(Debug, Clone, PartialEq)]
+pub enum PortRef {
+    Number(i32),
+    Name(String),
+}
+
+impl PortRef {
+    // parse treats a value that is a whole number as a port number and
+    // anything else as the name of a port in the `ports` block.
+    pub fn parse(value: &str) -> PortRef {
+        match value.parse::<i32>() {
+            Ok(number) => PortRef::Number(number),
+            Err(_) => PortRef::Name(value.to_string()),
+        }
+    }
+
+    // resolve returns the port number, looking names up in the deployment's ports.
+    pub fn resolve(&self, ports: &OrderedMap<String, i32>) -> Option<i32> {
+        match self {
+            PortRef::Number(number) => Some(*number),
+            PortRef::Name(name) => ports.get(name).copied(),
+        }
+    }
+}
+
+// Define the ProbeAction enum for the check a probe performs
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProbeAction {
+    Http {
+        path: String,
+        port: PortRef,
+    },
+    Tcp {
+        port: PortRef,
+    },
+    Exec {
+        command: Vec<String>,
+    },
+    Grpc {
+        port: PortRef,
+        service: Option<String>,
+    },
+}
+
+// Define the ProbeNode struct; durations are in whole seconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeNode {
+    pub action: ProbeAction,
+    pub initial_delay: Option<i32>,
+    pub period: Option<i32>,
+    pub timeout: Option<i32>,
+    pub failure_threshold: Option<i32>,
+    pub success_threshold: Option<i32>,
+    pub span: Span, // Location of the probe keyword in the source
+}
+
+// Define the ProbesNode struct
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProbesNode {
+    pub liveness: Option<ProbeNode>,
+    pub readiness: Option<ProbeNode>,
+    pub startup: Option<ProbeNode>,
+}
+
+impl ProbesNode {
+    // iter yields the probes that are set, together with their kind.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &ProbeNode)> {
+        [
+            ("liveness", &self.liveness),
+            ("readiness", &self.readiness),
+            ("startup", &self.startup),
+        ]
+        .into_iter()
+        .filter_map(|(kind, probe)| probe.as_ref().map(|probe| (kind, probe)))
+    }
+}
+
+// Define the EnvValue enum: a literal value or a key of a config or secret
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnvValue {
+    Value(String),
+    ConfigKey { config: String, key: String },
+    SecretKey { secret: String, key: String },
+}
+
+// Define the EnvSource enum for a config or secret whose keys all become env vars
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnvSource {
+    Config(String),
+    Secret(String),
 }
 
 // Define the DeploymentNode struct
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct DeploymentNode {
     pub name: String,
     pub namespace: String,
+    pub labels: OrderedMap<String, String>,
+    pub annotations: OrderedMap<String, String>,
     pub replicas: i32,
     pub image: String,
+    pub command: Vec<String>, // Overrides the image entrypoint
     pub args: Vec<String>,
-    pub env: HashMap<String, String>,
-    pub ports: HashMap<String, i32>,
+    pub env: OrderedMap<String, EnvValue>,
+    pub env_from: Vec<EnvSource>,
+    pub ports: OrderedMap<String, i32>,
     pub resources: Option<ResourceRequirementsNode>,
     pub storage: Option<StorageConfigNode>,
+    pub probes: ProbesNode,
+    pub autoscale: Option<AutoscaleNode>, // Replaces the fixed replica count when set
+    pub disruption: Option<DisruptionNode>,
+    pub network: Option<NetworkNode>,
+    pub service_account: Option<String>, // Identity the pods run as
+    pub fields: FieldSpans,              // Where the fields of the block are written
+    pub span: Span,                      // Location of the name in the source
 }
 
 // Implement the Node trait for DeploymentNode
@@ -48,44 +197,76 @@ mod tests {
     fn test_deployment_node_creation() {
         let resource_requirements = ResourceRequirementsNode {
             limits: ResourceSpec {
-                memory: "512Mi".to_string(),
-                cpu: "1".to_string(),
+                memory: Quantity::parse("512Mi"),
+                cpu: Quantity::parse("1"),
             },
             requests: ResourceSpec {
-                memory: "256Mi".to_string(),
-                cpu: "0.5".to_string(),
+                memory: Quantity::parse("256Mi"),
+                cpu: Quantity::parse("0.5"),
             },
         };
 
         let storage_config = StorageConfigNode {
             volume: "my-volume".to_string(),
-            size: "1Gi".to_string(),
+            size: Quantity::parse("1Gi").unwrap(),
+            mount_path: "/data".to_string(),
+            storage_class: None,
+            access_mode: AccessMode::ReadWriteOnce,
         };
 
         let deployment_node = DeploymentNode {
             name: "my-deployment".to_string(),
             namespace: "default".to_string(),
+            labels: OrderedMap::from([("tier".to_string(), "backend".to_string())]),
+            annotations: OrderedMap::new(),
             replicas: 3,
             image: "my-image".to_string(),
+            command: vec!["/bin/server".to_string()],
             args: vec!["arg1".to_string(), "arg2".to_string()],
-            env: [("ENV_VAR".to_string(), "value".to_string())]
-                .iter()
-                .cloned()
-                .collect(),
+            env: [
+                ("ENV_VAR".to_string(), EnvValue::Value("value".to_string())),
+                (
+                    "DB_PASS".to_string(),
+                    EnvValue::SecretKey {
+                        secret: "db-creds".to_string(),
+                        key: "password".to_string(),
+                    },
+                ),
+            ]
+            .iter()
+            .cloned()
+            .collect(),
+            env_from: vec![EnvSource::Config("app-config".to_string())],
             ports: [("http".to_string(), 80)].iter().cloned().collect(),
             resources: Some(resource_requirements),
             storage: Some(storage_config),
+            probes: ProbesNode::default(),
+            autoscale: None,
+            disruption: None,
+            network: None,
+            service_account: None,
+            fields: FieldSpans::new(),
+            span: Span::default(),
         };
 
         assert_eq!(deployment_node.name, "my-deployment");
         assert_eq!(deployment_node.namespace, "default");
+        assert_eq!(deployment_node.labels.get("tier").unwrap(), "backend");
         assert_eq!(deployment_node.replicas, 3);
         assert_eq!(deployment_node.image, "my-image");
+        assert_eq!(deployment_node.command, vec!["/bin/server".to_string()]);
         assert_eq!(
             deployment_node.args,
             vec!["arg1".to_string(), "arg2".to_string()]
         );
-        assert_eq!(deployment_node.env.get("ENV_VAR").unwrap(), "value");
+        assert_eq!(
+            deployment_node.env.get("ENV_VAR").unwrap(),
+            &EnvValue::Value("value".to_string())
+        );
+        assert_eq!(
+            deployment_node.env_from,
+            vec![EnvSource::Config("app-config".to_string())]
+        );
         assert_eq!(deployment_node.ports.get("http").unwrap(), &80);
         assert!(deployment_node.resources.is_some());
         assert!(deployment_node.storage.is_some());
@@ -96,15 +277,75 @@ mod tests {
         let deployment_node = DeploymentNode {
             name: "my-deployment".to_string(),
             namespace: "default".to_string(),
+            labels: OrderedMap::new(),
+            annotations: OrderedMap::new(),
             replicas: 3,
             image: "my-image".to_string(),
+            command: Vec::new(),
             args: vec!["arg1".to_string(), "arg2".to_string()],
-            env: HashMap::new(),
-            ports: HashMap::new(),
+            env: OrderedMap::new(),
+            env_from: Vec::new(),
+            ports: OrderedMap::new(),
             resources: None,
             storage: None,
+            probes: ProbesNode::default(),
+            autoscale: None,
+            disruption: None,
+            network: None,
+            service_account: None,
+            fields: FieldSpans::new(),
+            span: Span::default(),
         };
 
         assert_eq!(deployment_node.node_type(), "Deployment");
     }
+
+    #[test]
+    fn test_port_ref() {
+        let ports = OrderedMap::from([("http".to_string(), 8080)]);
+        assert_eq!(PortRef::parse("9090"), PortRef::Number(9090));
+        assert_eq!(PortRef::parse("http"), PortRef::Name("http".to_string()));
+        assert_eq!(PortRef::parse("http").resolve(&ports), Some(8080));
+        assert_eq!(PortRef::parse("9090").resolve(&ports), Some(9090));
+        assert_eq!(PortRef::parse("grpc").resolve(&ports), None);
+    }
+
+    #[test]
+    fn test_probes_iter() {
+        let probe = ProbeNode {
+            action: ProbeAction::Tcp {
+                port: PortRef::Number(80),
+            },
+            initial_delay: None,
+            period: Some(10),
+            timeout: None,
+            failure_threshold: None,
+            success_threshold: None,
+            span: Span::default(),
+        };
+        let probes = ProbesNode {
+            liveness: Some(probe.clone()),
+            readiness: None,
+            startup: Some(probe),
+        };
+        let kinds: Vec<&str> = probes.iter().map(|(kind, _)| kind).collect();
+        assert_eq!(kinds, vec!["liveness", "startup"]);
+        assert_eq!(ProbesNode::default().iter().count(), 0);
+    }
+
+    #[test]
+    fn test_access_mode_parse() {
+        assert_eq!(
+            AccessMode::parse("ReadWriteMany"),
+            Some(AccessMode::ReadWriteMany)
+        );
+        assert_eq!(AccessMode::parse("RWO"), Some(AccessMode::ReadWriteOnce));
+        assert_eq!(AccessMode::parse("ROX"), Some(AccessMode::ReadOnlyMany));
+        assert_eq!(
+            AccessMode::parse("RWOP"),
+            Some(AccessMode::ReadWriteOncePod)
+        );
+        assert_eq!(AccessMode::parse("readwriteonce"), None);
+        assert_eq!(AccessMode::default().as_str(), "ReadWriteOnce");
+    }
 }
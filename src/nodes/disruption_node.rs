@@ -0,0 +1,74 @@
+use crate::lexer::lexer::Span;
+use std::fmt;
+
+// Define the DisruptionValue enum for a number of pods, or a percentage of the
+// workload's replicas
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisruptionValue {
+    Count(i32),
+    Percent(i32),
+}
+
+impl fmt::Display for DisruptionValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DisruptionValue::Count(count) => write!(f, "{}", count),
+            DisruptionValue::Percent(percent) => write!(f, "{}%", percent),
+        }
+    }
+}
+
+// Define the DisruptionBudget enum; a budget sets exactly one of the two bounds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisruptionBudget {
+    MinAvailable(DisruptionValue),
+    MaxUnavailable(DisruptionValue),
+}
+
+impl DisruptionBudget {
+    // field returns the PodDisruptionBudget spec field of the bound.
+    pub fn field(&self) -> &'static str {
+        match self {
+            DisruptionBudget::MinAvailable(_) => "minAvailable",
+            DisruptionBudget::MaxUnavailable(_) => "maxUnavailable",
+        }
+    }
+
+    // value returns the bound's number or percentage.
+    pub fn value(&self) -> DisruptionValue {
+        match self {
+            DisruptionBudget::MinAvailable(value) | DisruptionBudget::MaxUnavailable(value) => {
+                *value
+            }
+        }
+    }
+}
+
+// Define the DisruptionNode struct, how many pods of a workload voluntary
+// evictions such as node drains may take down at once
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisruptionNode {
+    pub budget: DisruptionBudget,
+    pub span: Span, // Location of the disruption keyword in the source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disruption_value_display() {
+        assert_eq!(DisruptionValue::Count(1).to_string(), "1");
+        assert_eq!(DisruptionValue::Percent(25).to_string(), "25%");
+    }
+
+    #[test]
+    fn test_disruption_budget() {
+        let budget = DisruptionBudget::MinAvailable(DisruptionValue::Count(2));
+        assert_eq!(budget.field(), "minAvailable");
+        assert_eq!(budget.value(), DisruptionValue::Count(2));
+        let budget = DisruptionBudget::MaxUnavailable(DisruptionValue::Percent(25));
+        assert_eq!(budget.field(), "maxUnavailable");
+        assert_eq!(budget.value(), DisruptionValue::Percent(25));
+    }
+}
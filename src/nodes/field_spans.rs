@@ -0,0 +1,67 @@
+use crate::lexer::lexer::Span;
+use crate::nodes::ordered_map::OrderedMap;
+
+// FieldSpans records where the fields of a block are written in the source, so
+// that problems found after parsing point at the field rather than at the name
+// of the block. Fields are keyed by their path, e.g. `replicas`, `ports.http` or
+// `resources.limits.cpu`. A field written more than once keeps every span.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FieldSpans {
+    spans: OrderedMap<String, Vec<Span>>,
+}
+
+impl FieldSpans {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // insert records one occurrence of a field.
+    pub fn insert(&mut self, path: impl Into<String>, span: Span) {
+        let path = path.into();
+        let mut spans = self.spans.get(&path).cloned().unwrap_or_default();
+        spans.push(span);
+        self.spans.insert(path, spans);
+    }
+
+    // get returns the span of the last occurrence of a field, which holds its
+    // value, or `fallback` for a field that is not written in the source.
+    pub fn get(&self, path: &str, fallback: Span) -> Span {
+        self.spans
+            .get(path)
+            .and_then(|spans| spans.last().copied())
+            .unwrap_or(fallback)
+    }
+
+    // repeated lists the fields written more than once with their spans, in
+    // source order.
+    pub fn repeated(&self) -> impl Iterator<Item = (&String, &Vec<Span>)> + '_ {
+        self.spans.iter().filter(|(_, spans)| spans.len() > 1)
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let mut fields = FieldSpans::new();
+        fields.insert("replicas", Span::new(10, 21));
+        assert_eq!(fields.get("replicas", Span::new(0, 3)), Span::new(10, 21));
+        assert_eq!(fields.get("image", Span::new(0, 3)), Span::new(0, 3));
+    }
+
+    #[test]
+    fn test_repeated() {
+        let mut fields = FieldSpans::new();
+        fields.insert("ports.http", Span::new(10, 18));
+        fields.insert("ports.grpc", Span::new(20, 30));
+        fields.insert("ports.http", Span::new(40, 48));
+        assert_eq!(fields.get("ports.http", Span::default()), Span::new(40, 48));
+        let repeated: Vec<(&String, &Vec<Span>)> = fields.repeated().collect();
+        assert_eq!(repeated.len(), 1);
+        assert_eq!(repeated[0].0, "ports.http");
+        assert_eq!(repeated[0].1, &vec![Span::new(10, 18), Span::new(40, 48)]);
+    }
+}
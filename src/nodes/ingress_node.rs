@@ -0,0 +1,62 @@
+use crate::lexer::lexer::Span;
+use crate::nodes::deployment_node::PortRef;
+use crate::nodes::field_spans::FieldSpans;
+use crate::nodes::node::Node;
+use crate::nodes::ordered_map::OrderedMap;
+
+// Define the IngressBackend struct, the service port a route sends requests to.
+// A named port is the name of a container port of the app behind the service.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngressBackend {
+    pub service: String,
+    pub port: PortRef,
+    pub span: Span, // Location of the `service(...)` reference in the source
+}
+
+// Define the IngressNode struct
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct IngressNode {
+    pub name: String,
+    pub namespace: String,
+    pub labels: OrderedMap<String, String>,
+    pub annotations: OrderedMap<String, String>,
+    pub host: Option<String>,       // Requests for any host match when unset
+    pub tls_secret: Option<String>, // Secret holding the certificate for the host
+    pub routes: OrderedMap<String, IngressBackend>, // Map of `path,backend`
+    pub fields: FieldSpans,         // Where the fields of the block are written
+    pub span: Span,                 // Location of the name in the source
+}
+
+// Implement the Node trait for IngressNode
+impl Node for IngressNode {
+    fn node_type(&self) -> String {
+        "Ingress".to_string()
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ingress_node_type() {
+        let ingress = IngressNode {
+            name: "api".to_string(),
+            namespace: "default".to_string(),
+            host: Some("api.example.com".to_string()),
+            tls_secret: Some("api-tls".to_string()),
+            routes: OrderedMap::from([(
+                "/".to_string(),
+                IngressBackend {
+                    service: "api".to_string(),
+                    port: PortRef::Name("http".to_string()),
+                    span: Span::default(),
+                },
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(ingress.node_type(), "Ingress");
+        assert_eq!(ingress.routes.get("/").unwrap().service, "api");
+    }
+}
@@ -0,0 +1,143 @@
+use crate::nodes::deployment_node::DeploymentNode;
+use crate::nodes::node::Node;
+
+// Define the RestartPolicy enum; jobs cannot restart containers that succeed
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum RestartPolicy {
+    #[default]
+    Never, // A failed pod is replaced by a new one
+    OnFailure, // A failed container is restarted in the same pod
+}
+
+impl RestartPolicy {
+    // parse accepts the Kubernetes policy names, ignoring case.
+    pub fn parse(value: &str) -> Option<RestartPolicy> {
+        match value.to_ascii_lowercase().as_str() {
+            "never" => Some(RestartPolicy::Never),
+            "onfailure" => Some(RestartPolicy::OnFailure),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RestartPolicy::Never => "Never",
+            RestartPolicy::OnFailure => "OnFailure",
+        }
+    }
+}
+
+// Define the ConcurrencyPolicy enum for a cronjob run that is due while the
+// previous one is still running
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConcurrencyPolicy {
+    Allow,
+    Forbid,  // Skip the new run
+    Replace, // Stop the running job and start the new one
+}
+
+impl ConcurrencyPolicy {
+    // parse accepts the Kubernetes policy names, ignoring case.
+    pub fn parse(value: &str) -> Option<ConcurrencyPolicy> {
+        match value.to_ascii_lowercase().as_str() {
+            "allow" => Some(ConcurrencyPolicy::Allow),
+            "forbid" => Some(ConcurrencyPolicy::Forbid),
+            "replace" => Some(ConcurrencyPolicy::Replace),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConcurrencyPolicy::Allow => "Allow",
+            ConcurrencyPolicy::Forbid => "Forbid",
+            ConcurrencyPolicy::Replace => "Replace",
+        }
+    }
+}
+
+// Define the JobNode struct. The container and metadata fields are those of a
+// deployment; its replica count is not used.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct JobNode {
+    pub deployment: DeploymentNode,
+    pub backoff_limit: Option<i32>, // Retries before the job fails
+    pub completions: Option<i32>,
+    pub parallelism: Option<i32>,
+    pub active_deadline_seconds: Option<i32>,
+    pub restart_policy: RestartPolicy,
+}
+
+// Define the CronJobNode struct, a job run on a cron schedule
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CronJobNode {
+    pub job: JobNode,
+    pub schedule: String, // Checked with cron::check_schedule when parsed
+    pub concurrency_policy: Option<ConcurrencyPolicy>,
+}
+
+// Implement the Node trait for JobNode
+impl Node for JobNode {
+    fn node_type(&self) -> String {
+        "Job".to_string()
+    }
+}
+
+// Implement the Node trait for CronJobNode
+impl Node for CronJobNode {
+    fn node_type(&self) -> String {
+        "CronJob".to_string()
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_node_type() {
+        let job = JobNode {
+            deployment: DeploymentNode {
+                name: "migrate".to_string(),
+                ..Default::default()
+            },
+            backoff_limit: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(job.node_type(), "Job");
+        assert_eq!(job.restart_policy, RestartPolicy::Never);
+        let cronjob = CronJobNode {
+            job,
+            schedule: "0 3 * * *".to_string(),
+            concurrency_policy: None,
+        };
+        assert_eq!(cronjob.node_type(), "CronJob");
+        assert_eq!(cronjob.job.deployment.name, "migrate");
+    }
+
+    #[test]
+    fn test_restart_policy_parse() {
+        assert_eq!(
+            RestartPolicy::parse("OnFailure"),
+            Some(RestartPolicy::OnFailure)
+        );
+        assert_eq!(RestartPolicy::parse("never"), Some(RestartPolicy::Never));
+        assert_eq!(RestartPolicy::parse("Always"), None);
+        assert_eq!(RestartPolicy::OnFailure.as_str(), "OnFailure");
+    }
+
+    #[test]
+    fn test_concurrency_policy_parse() {
+        assert_eq!(
+            ConcurrencyPolicy::parse("Forbid"),
+            Some(ConcurrencyPolicy::Forbid)
+        );
+        assert_eq!(
+            ConcurrencyPolicy::parse("replace"),
+            Some(ConcurrencyPolicy::Replace)
+        );
+        assert_eq!(ConcurrencyPolicy::parse("queue"), None);
+        assert_eq!(ConcurrencyPolicy::Allow.as_str(), "Allow");
+    }
+}
@@ -0,0 +1,147 @@
+use crate::lexer::lexer::Span;
+use crate::nodes::deployment_node::PortRef;
+use std::net::IpAddr;
+
+// Define the Direction enum for the traffic a network rule governs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Ingress, // Traffic into the workload's pods
+    Egress,  // Traffic out of the workload's pods
+}
+
+impl Direction {
+    // as_str returns the NetworkPolicy policy type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Ingress => "Ingress",
+            Direction::Egress => "Egress",
+        }
+    }
+}
+
+// Define the NetworkPeer enum for the other end of a network rule
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkPeer {
+    App(String),  // The pods of a workload in the same namespace
+    Cidr(String), // A range of IP addresses, such as `10.0.0.0/8`
+}
+
+// Define the NetworkRule struct, an `allow from ...` or `allow to ...` rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkRule {
+    pub direction: Direction,
+    pub peers: Vec<NetworkPeer>,
+    pub ports: Vec<PortRef>, // Empty allows every port
+    pub span: Span,          // Location of the allow keyword in the source
+}
+
+// Define the NetworkNode struct, the traffic a workload's pods accept and send.
+// Once a direction has a rule or is denied, all other traffic in that
+// direction is blocked.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkNode {
+    pub rules: Vec<NetworkRule>,
+    pub denied: Vec<Direction>, // Directions closed by `deny all ingress|egress`
+    pub span: Span,             // Location of the network keyword in the source
+}
+
+impl NetworkNode {
+    // policy_types returns the directions the block restricts, ingress first.
+    pub fn policy_types(&self) -> Vec<Direction> {
+        [Direction::Ingress, Direction::Egress]
+            .into_iter()
+            .filter(|direction| {
+                self.denied.contains(direction)
+                    || self.rules.iter().any(|rule| rule.direction == *direction)
+            })
+            .collect()
+    }
+
+    // rules_for returns the rules of one direction in source order.
+    pub fn rules_for(&self, direction: Direction) -> impl Iterator<Item = &NetworkRule> {
+        self.rules
+            .iter()
+            .filter(move |rule| rule.direction == direction)
+    }
+}
+
+// is_cidr reports whether a value is an IPv4 or IPv6 address followed by a
+// prefix length that fits the address, such as `10.0.0.0/8` or `fd00::/8`.
+pub fn is_cidr(value: &str) -> bool {
+    let Some((address, prefix)) = value.split_once('/') else {
+        return false;
+    };
+    let bits = match address.parse::<IpAddr>() {
+        Ok(IpAddr::V4(_)) => 32,
+        Ok(IpAddr::V6(_)) => 128,
+        Err(_) => return false,
+    };
+    prefix.bytes().all(|b| b.is_ascii_digit())
+        && prefix.parse::<u8>().is_ok_and(|prefix| prefix <= bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(direction: Direction) -> NetworkRule {
+        NetworkRule {
+            direction,
+            peers: vec![NetworkPeer::App("frontend".to_string())],
+            ports: vec![PortRef::Name("http".to_string())],
+            span: Span::new(0, 5),
+        }
+    }
+
+    #[test]
+    fn test_policy_types() {
+        let network = NetworkNode {
+            rules: vec![rule(Direction::Ingress)],
+            ..Default::default()
+        };
+        assert_eq!(network.policy_types(), vec![Direction::Ingress]);
+        let network = NetworkNode {
+            denied: vec![Direction::Egress, Direction::Ingress],
+            ..Default::default()
+        };
+        assert_eq!(
+            network.policy_types(),
+            vec![Direction::Ingress, Direction::Egress]
+        );
+        assert!(NetworkNode::default().policy_types().is_empty());
+    }
+
+    #[test]
+    fn test_rules_for() {
+        let network = NetworkNode {
+            rules: vec![
+                rule(Direction::Egress),
+                rule(Direction::Ingress),
+                rule(Direction::Egress),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(network.rules_for(Direction::Ingress).count(), 1);
+        assert_eq!(network.rules_for(Direction::Egress).count(), 2);
+    }
+
+    #[test]
+    fn test_is_cidr() {
+        assert!(is_cidr("10.0.0.0/8"));
+        assert!(is_cidr("192.168.1.10/32"));
+        assert!(is_cidr("0.0.0.0/0"));
+        assert!(is_cidr("fd00::/8"));
+        assert!(!is_cidr("10.0.0.0"));
+        assert!(!is_cidr("10.0.0.0/33"));
+        assert!(!is_cidr("10.0.0/8"));
+        assert!(!is_cidr("10.0.0.0/+8"));
+        assert!(!is_cidr("fd00::/129"));
+        assert!(!is_cidr("example.com/8"));
+    }
+
+    #[test]
+    fn test_direction_as_str() {
+        assert_eq!(Direction::Ingress.as_str(), "Ingress");
+        assert_eq!(Direction::Egress.as_str(), "Egress");
+    }
+}
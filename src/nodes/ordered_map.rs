@@ -0,0 +1,142 @@
+use std::borrow::Borrow;
+
+// OrderedMap is a map that keeps its entries in insertion order, so that nodes
+// preserve the order of the source and generated manifests are the same on
+// every run. Lookups are linear, which suits the few entries of a block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderedMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        OrderedMap {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<K: PartialEq, V> OrderedMap<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // insert sets the value of a key and returns the previous one. A new key is
+    // added at the end; an existing key keeps its position.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => Some(std::mem::replace(existing, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.borrow() == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+}
+
+impl<K, V> OrderedMap<K, V> {
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.entries.iter().map(|(_, v)| v)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<K: PartialEq, V> FromIterator<(K, V)> for OrderedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = OrderedMap::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> From<[(K, V); N]> for OrderedMap<K, V> {
+    fn from(entries: [(K, V); N]) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_insertion_order() {
+        let mut map = OrderedMap::new();
+        map.insert("b".to_string(), 2);
+        map.insert("a".to_string(), 1);
+        map.insert("c".to_string(), 3);
+        let keys: Vec<&str> = map.keys().map(|k| k.as_str()).collect();
+        assert_eq!(keys, vec!["b", "a", "c"]);
+        assert_eq!(map.values().copied().collect::<Vec<i32>>(), vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn test_insert_replaces_in_place() {
+        let mut map = OrderedMap::from([("http", 80), ("metrics", 9090)]);
+        assert_eq!(map.insert("http", 8080), Some(80));
+        assert_eq!(map.insert("grpc", 9000), None);
+        let entries: Vec<(&&str, &i32)> = map.iter().collect();
+        assert_eq!(
+            entries,
+            vec![(&"http", &8080), (&"metrics", &9090), (&"grpc", &9000)]
+        );
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let map: OrderedMap<String, String> =
+            [("LOG_LEVEL".to_string(), "debug".to_string())].into();
+        assert_eq!(map.get("LOG_LEVEL").map(|v| v.as_str()), Some("debug"));
+        assert!(map.contains_key("LOG_LEVEL"));
+        assert!(!map.contains_key("MODE"));
+        assert!(!map.is_empty());
+        assert!(OrderedMap::<String, String>::new().is_empty());
+    }
+
+    #[test]
+    fn test_from_iter_keeps_first_position() {
+        let map: OrderedMap<i32, i32> = vec![(80, 8080), (443, 8443), (80, 80)]
+            .into_iter()
+            .collect();
+        let entries: Vec<(&i32, &i32)> = map.iter().collect();
+        assert_eq!(entries, vec![(&80, &80), (&443, &8443)]);
+    }
+}
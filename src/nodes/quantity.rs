@@ -0,0 +1,243 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+// Number of nano units in one unit; quantities are stored as whole nano units,
+// the smallest fraction Kubernetes keeps.
+const NANOS: i128 = 1_000_000_000;
+
+// Decimal SI suffixes and their powers of ten, from largest to smallest.
+const DECIMAL_SUFFIXES: [(&str, i32); 10] = [
+    ("E", 18),
+    ("P", 15),
+    ("T", 12),
+    ("G", 9),
+    ("M", 6),
+    ("k", 3),
+    ("", 0),
+    ("m", -3),
+    ("u", -6),
+    ("n", -9),
+];
+
+// Binary SI suffixes and their powers of 1024, from largest to smallest.
+const BINARY_SUFFIXES: [(&str, u32); 6] = [
+    ("Ei", 6),
+    ("Pi", 5),
+    ("Ti", 4),
+    ("Gi", 3),
+    ("Mi", 2),
+    ("Ki", 1),
+];
+
+// Define the QuantityFormat enum, the notation a quantity was written in.
+// It is kept so that a quantity is printed back in the same style.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum QuantityFormat {
+    #[default]
+    DecimalSI, // 500m, 1k, 2G
+    BinarySI,        // 512Mi, 1Gi
+    DecimalExponent, // 1e3, 2.5E-3
+}
+
+// Quantity is a Kubernetes resource quantity such as `512Mi`, `250m` or `1e3`.
+// Quantities compare by value, so `1Gi` equals `1024Mi` and `0.5` equals `500m`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Quantity {
+    nanos: i128,
+    format: QuantityFormat,
+}
+
+impl Quantity {
+    // parse reads a non-negative number with an optional decimal fraction,
+    // followed by an optional binary suffix (Ki..Ei), decimal suffix (n, u, m,
+    // k, M..E) or exponent (e3, E-6). Fractions below one nano are rounded up.
+    pub fn parse(value: &str) -> Option<Quantity> {
+        let number_len = value
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(value.len());
+        let (number, suffix) = value.split_at(number_len);
+        let (digits, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if digits.is_empty() && fraction.is_empty() {
+            return None;
+        }
+
+        let (format, exponent, binary) = if let Some(&(_, power)) =
+            BINARY_SUFFIXES.iter().find(|(s, _)| *s == suffix)
+        {
+            (QuantityFormat::BinarySI, 0, power)
+        } else if let Some(&(_, exponent)) = DECIMAL_SUFFIXES.iter().find(|(s, _)| *s == suffix) {
+            (QuantityFormat::DecimalSI, exponent, 0)
+        } else if let Some(exponent) = suffix.strip_prefix(['e', 'E']) {
+            let exponent = exponent.parse::<i32>().ok()?;
+            (QuantityFormat::DecimalExponent, exponent, 0)
+        } else {
+            return None;
+        };
+
+        // Scale the digits to nano units: mantissa * 10^(exponent + 9 - fraction digits).
+        let mantissa: i128 = format!("{}{}", digits, fraction).parse().ok()?;
+        let scale = exponent + 9 - fraction.len() as i32;
+        let mut nanos = if scale >= 0 {
+            mantissa.checked_mul(10i128.checked_pow(scale as u32)?)?
+        } else {
+            let divisor = 10i128.checked_pow(scale.unsigned_abs())?;
+            mantissa.checked_add(divisor - 1)? / divisor
+        };
+        nanos = nanos.checked_mul(1024i128.checked_pow(binary)?)?;
+        Some(Quantity { nanos, format })
+    }
+
+    // format returns the notation the quantity was written in.
+    pub fn format(&self) -> QuantityFormat {
+        self.format
+    }
+
+    // is_zero reports whether the quantity has no value.
+    pub fn is_zero(&self) -> bool {
+        self.nanos == 0
+    }
+}
+
+// Display prints the canonical form of a quantity, the way the API server
+// normalizes it: the largest suffix that keeps the number whole, in the
+// quantity's own notation. Binary quantities that are not a whole multiple of
+// 1024 fall back to decimal suffixes.
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.nanos == 0 {
+            return write!(f, "0");
+        }
+        if self.format == QuantityFormat::BinarySI && self.nanos % NANOS == 0 {
+            let units = self.nanos / NANOS;
+            for (suffix, power) in BINARY_SUFFIXES {
+                let size = 1024i128.pow(power);
+                if units % size == 0 {
+                    return write!(f, "{}{}", units / size, suffix);
+                }
+            }
+        }
+
+        for (suffix, exponent) in DECIMAL_SUFFIXES {
+            let size = 10i128.pow((exponent + 9) as u32);
+            if self.nanos % size == 0 {
+                let value = self.nanos / size;
+                return match self.format {
+                    QuantityFormat::DecimalExponent if exponent != 0 => {
+                        write!(f, "{}e{}", value, exponent)
+                    }
+                    QuantityFormat::DecimalExponent => write!(f, "{}", value),
+                    _ => write!(f, "{}{}", value, suffix),
+                };
+            }
+        }
+        unreachable!("every value is a whole number of nano units")
+    }
+}
+
+impl PartialEq for Quantity {
+    fn eq(&self, other: &Self) -> bool {
+        self.nanos == other.nanos
+    }
+}
+
+impl Eq for Quantity {}
+
+impl PartialOrd for Quantity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Quantity {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.nanos.cmp(&other.nanos)
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q(value: &str) -> Quantity {
+        Quantity::parse(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        assert_eq!(q("512Mi").format(), QuantityFormat::BinarySI);
+        assert_eq!(q("500m").format(), QuantityFormat::DecimalSI);
+        assert_eq!(q("2").format(), QuantityFormat::DecimalSI);
+        assert_eq!(q("1e3").format(), QuantityFormat::DecimalExponent);
+        assert_eq!(q("1E").format(), QuantityFormat::DecimalSI);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for value in [
+            "", "lots", "1Xi", "Mi", "1.2.3", "-1", "1 Gi", "1e", "1ee3", ".",
+        ] {
+            assert_eq!(Quantity::parse(value), None, "parsing {:?}", value);
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(q("1Gi"), q("1024Mi"));
+        assert_eq!(q("0.5"), q("500m"));
+        assert_eq!(q("1k"), q("1e3"));
+        assert!(q("1Gi") > q("1G"));
+        assert!(q("250m") < q("1"));
+        assert!(q("2048Mi") > q("1Gi"));
+        assert!(q("1n") > q("0"));
+    }
+
+    #[test]
+    fn test_display_canonical() {
+        let cases = vec![
+            ("512Mi", "512Mi"),
+            ("1024Mi", "1Gi"),
+            ("1.5Gi", "1536Mi"),
+            ("1.5Ki", "1536"),
+            ("0.5", "500m"),
+            ("1000m", "1"),
+            ("1500", "1500"),
+            ("2000", "2k"),
+            ("0.1m", "100u"),
+            ("1e3", "1e3"),
+            ("1.5e3", "1500"),
+            ("2.5E-3", "2500e-6"),
+            ("0", "0"),
+            ("0Gi", "0"),
+            (".5", "500m"),
+            ("5.", "5"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(q(input).to_string(), expected, "normalizing {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_rounds_up_below_nano() {
+        assert_eq!(q("0.1n").to_string(), "1n");
+        assert!(!q("0.1n").is_zero());
+        assert!(q("0").is_zero());
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(Quantity::parse("1e100"), None);
+        assert_eq!(
+            Quantity::parse("99999999999999999999999999999999999999999"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_overflow_rounding_up() {
+        assert_eq!(
+            Quantity::parse("170141183460469231731687303715884105727e-10"),
+            None
+        );
+    }
+}
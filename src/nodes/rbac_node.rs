@@ -0,0 +1,184 @@
+use crate::lexer::lexer::Span;
+use crate::nodes::field_spans::FieldSpans;
+use crate::nodes::node::Node;
+use crate::nodes::ordered_map::OrderedMap;
+
+// Define the ApiResource struct, a resource that role rules can grant access to
+#[derive(Debug, PartialEq)]
+pub struct ApiResource {
+    pub name: &'static str,  // Plural name, with the subresource after a `/`
+    pub group: &'static str, // API group; empty for the core group
+    pub namespaced: bool,    // Whether a namespaced Role can grant it
+}
+
+// Resources of the core API groups that roles can refer to.
+pub const API_RESOURCES: [ApiResource; 30] = [
+    resource("pods", "", true),
+    resource("pods/log", "", true),
+    resource("pods/exec", "", true),
+    resource("pods/portforward", "", true),
+    resource("services", "", true),
+    resource("endpoints", "", true),
+    resource("configmaps", "", true),
+    resource("secrets", "", true),
+    resource("persistentvolumeclaims", "", true),
+    resource("serviceaccounts", "", true),
+    resource("events", "", true),
+    resource("namespaces", "", false),
+    resource("nodes", "", false),
+    resource("persistentvolumes", "", false),
+    resource("deployments", "apps", true),
+    resource("deployments/scale", "apps", true),
+    resource("statefulsets", "apps", true),
+    resource("daemonsets", "apps", true),
+    resource("replicasets", "apps", true),
+    resource("jobs", "batch", true),
+    resource("cronjobs", "batch", true),
+    resource("horizontalpodautoscalers", "autoscaling", true),
+    resource("poddisruptionbudgets", "policy", true),
+    resource("ingresses", "networking.k8s.io", true),
+    resource("networkpolicies", "networking.k8s.io", true),
+    resource("endpointslices", "discovery.k8s.io", true),
+    resource("leases", "coordination.k8s.io", true),
+    resource("roles", "rbac.authorization.k8s.io", true),
+    resource("rolebindings", "rbac.authorization.k8s.io", true),
+    resource("storageclasses", "storage.k8s.io", false),
+];
+
+// Verbs that role rules can grant.
+pub const VERBS: [&str; 8] = [
+    "get",
+    "list",
+    "watch",
+    "create",
+    "update",
+    "patch",
+    "delete",
+    "deletecollection",
+];
+
+const fn resource(name: &'static str, group: &'static str, namespaced: bool) -> ApiResource {
+    ApiResource {
+        name,
+        group,
+        namespaced,
+    }
+}
+
+// api_resource looks a resource up in API_RESOURCES by name.
+pub fn api_resource(name: &str) -> Option<&'static ApiResource> {
+    API_RESOURCES.iter().find(|resource| resource.name == name)
+}
+
+// Define the RoleScope enum for where the permissions of a role apply
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum RoleScope {
+    #[default]
+    Namespace, // A Role, granting access within its own namespace
+    Cluster, // A ClusterRole, granting access in every namespace
+}
+
+impl RoleScope {
+    // parse reads a scope as written in a script.
+    pub fn parse(value: &str) -> Option<RoleScope> {
+        match value {
+            "namespace" => Some(RoleScope::Namespace),
+            "cluster" => Some(RoleScope::Cluster),
+            _ => None,
+        }
+    }
+}
+
+// Define the RoleRule struct, the verbs a role allows on one resource
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoleRule {
+    pub verbs: Vec<String>,
+    pub span: Span, // Location of the resource name in the source
+}
+
+// Define the RoleNode struct
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RoleNode {
+    pub name: String,
+    pub namespace: String, // Unused by a cluster role
+    pub labels: OrderedMap<String, String>,
+    pub annotations: OrderedMap<String, String>,
+    pub scope: RoleScope,
+    pub rules: OrderedMap<String, RoleRule>, // Map of `resource,rule`
+    pub fields: FieldSpans,                  // Where the fields of the block are written
+    pub span: Span,                          // Location of the name in the source
+}
+
+impl RoleNode {
+    // kind returns the kind of the object the role generates.
+    pub fn kind(&self) -> &'static str {
+        match self.scope {
+            RoleScope::Namespace => "Role",
+            RoleScope::Cluster => "ClusterRole",
+        }
+    }
+}
+
+// Implement the Node trait for RoleNode
+impl Node for RoleNode {
+    fn node_type(&self) -> String {
+        self.kind().to_string()
+    }
+}
+
+// Define the BindingNode struct, a `bind <role> to <service account>;` statement
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BindingNode {
+    pub role: String,
+    pub service_account: String,
+    pub span: Span, // Location of the role name in the source
+}
+
+// Implement the Node trait for BindingNode
+impl Node for BindingNode {
+    fn node_type(&self) -> String {
+        "Binding".to_string()
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_resource() {
+        let deployments = api_resource("deployments").unwrap();
+        assert_eq!(deployments.group, "apps");
+        assert!(deployments.namespaced);
+        assert_eq!(api_resource("pods/log").unwrap().group, "");
+        assert!(!api_resource("nodes").unwrap().namespaced);
+        assert_eq!(api_resource("pod"), None);
+    }
+
+    #[test]
+    fn test_api_resources_are_unique() {
+        for (i, resource) in API_RESOURCES.iter().enumerate() {
+            assert_eq!(api_resource(resource.name), Some(&API_RESOURCES[i]));
+        }
+    }
+
+    #[test]
+    fn test_role_scope_parse() {
+        assert_eq!(RoleScope::parse("namespace"), Some(RoleScope::Namespace));
+        assert_eq!(RoleScope::parse("cluster"), Some(RoleScope::Cluster));
+        assert_eq!(RoleScope::parse("global"), None);
+    }
+
+    #[test]
+    fn test_role_node_type() {
+        let mut role = RoleNode {
+            name: "reader".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(role.node_type(), "Role");
+        role.scope = RoleScope::Cluster;
+        assert_eq!(role.node_type(), "ClusterRole");
+        assert_eq!(BindingNode::default().node_type(), "Binding");
+    }
+}
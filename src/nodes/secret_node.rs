@@ -0,0 +1,52 @@
+use crate::lexer::lexer::Span;
+use crate::nodes::field_spans::FieldSpans;
+use crate::nodes::node::Node;
+use crate::nodes::ordered_map::OrderedMap;
+
+// Secret type used when a secret block does not declare one.
+pub const DEFAULT_SECRET_TYPE: &str = "Opaque";
+
+// Define the SecretNode struct
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SecretNode {
+    pub name: String,
+    pub namespace: String,
+    pub secret_type: String,
+    pub labels: OrderedMap<String, String>,
+    pub annotations: OrderedMap<String, String>,
+    pub data: OrderedMap<String, String>, // Values in plain text, rendered as `stringData`
+    pub fields: FieldSpans,               // Where the fields of the block are written
+    pub span: Span,                       // Location of the name in the source
+}
+
+// Implement the Node trait for SecretNode
+impl Node for SecretNode {
+    fn node_type(&self) -> String {
+        "Secret".to_string()
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_node_creation() {
+        let secret_node = SecretNode {
+            name: "db-creds".to_string(),
+            namespace: "default".to_string(),
+            secret_type: DEFAULT_SECRET_TYPE.to_string(),
+            labels: OrderedMap::new(),
+            annotations: OrderedMap::new(),
+            data: OrderedMap::from([("password".to_string(), "hunter2".to_string())]),
+            fields: FieldSpans::new(),
+            span: Span::default(),
+        };
+
+        assert_eq!(secret_node.name, "db-creds");
+        assert_eq!(secret_node.secret_type, "Opaque");
+        assert_eq!(secret_node.data.get("password").unwrap(), "hunter2");
+        assert_eq!(secret_node.node_type(), "Secret");
+    }
+}
@@ -1,12 +1,59 @@
+use crate::lexer::lexer::Span;
+use crate::nodes::field_spans::FieldSpans;
 use crate::nodes::node::Node;
-use std::collections::HashMap;
+use crate::nodes::ordered_map::OrderedMap;
+
+// Define the ServiceType enum
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ServiceType {
+    #[default]
+    ClusterIP,
+    NodePort,
+    LoadBalancer,
+    Headless, // ClusterIP service without a cluster IP
+}
+
+impl ServiceType {
+    // parse accepts the Kubernetes type names, ignoring case, plus `headless`.
+    pub fn parse(value: &str) -> Option<ServiceType> {
+        match value.to_ascii_lowercase().as_str() {
+            "clusterip" => Some(ServiceType::ClusterIP),
+            "nodeport" => Some(ServiceType::NodePort),
+            "loadbalancer" => Some(ServiceType::LoadBalancer),
+            "headless" => Some(ServiceType::Headless),
+            _ => None,
+        }
+    }
+
+    // as_str returns the value of the Service `type` field.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServiceType::ClusterIP | ServiceType::Headless => "ClusterIP",
+            ServiceType::NodePort => "NodePort",
+            ServiceType::LoadBalancer => "LoadBalancer",
+        }
+    }
+}
 
 // Define the ServiceNode struct
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct ServiceNode {
     pub name: String,
     pub namespace: String,
-    pub ports: HashMap<i32, i32>, // Map of `port,targetPort`
-    pub labels: HashMap<String, String>,
+    pub service_type: ServiceType,
+    pub ports: OrderedMap<i32, i32>, // Map of `port,targetPort`
+    pub labels: OrderedMap<String, String>,
+    pub annotations: OrderedMap<String, String>,
+    pub app: Option<String>, // Deployment the service selects, by default its own name
+    pub fields: FieldSpans,  // Where the fields of the block are written
+    pub span: Span,          // Location of the name in the source
+}
+
+impl ServiceNode {
+    // target returns the name of the app whose pods the service selects.
+    pub fn target(&self) -> &str {
+        self.app.as_deref().unwrap_or(&self.name)
+    }
 }
 
 // Implement the Node trait for ServiceNode
@@ -15,3 +62,51 @@ impl Node for ServiceNode {
         "Service".to_string()
     }
 }
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_node_type() {
+        let service_node = ServiceNode {
+            name: "my-svc".to_string(),
+            namespace: "default".to_string(),
+            service_type: ServiceType::ClusterIP,
+            ports: [(80, 8080)].iter().cloned().collect(),
+            labels: OrderedMap::new(),
+            annotations: OrderedMap::new(),
+            app: None,
+            fields: FieldSpans::new(),
+            span: Span::default(),
+        };
+
+        assert_eq!(service_node.node_type(), "Service");
+        assert_eq!(service_node.ports.get(&80).unwrap(), &8080);
+    }
+
+    #[test]
+    fn test_service_target() {
+        let mut service_node = ServiceNode {
+            name: "web".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(service_node.target(), "web");
+        service_node.app = Some("storefront".to_string());
+        assert_eq!(service_node.target(), "storefront");
+    }
+
+    #[test]
+    fn test_service_type_parse() {
+        assert_eq!(ServiceType::parse("NodePort"), Some(ServiceType::NodePort));
+        assert_eq!(
+            ServiceType::parse("loadbalancer"),
+            Some(ServiceType::LoadBalancer)
+        );
+        assert_eq!(ServiceType::parse("headless"), Some(ServiceType::Headless));
+        assert_eq!(ServiceType::parse("ExternalName"), None);
+        assert_eq!(ServiceType::Headless.as_str(), "ClusterIP");
+        assert_eq!(ServiceType::default(), ServiceType::ClusterIP);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::nodes::deployment_node::DeploymentNode;
+use crate::nodes::node::Node;
+
+// Define the PodManagementPolicy enum
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PodManagementPolicy {
+    OrderedReady, // Pods start and stop one at a time, in order
+    Parallel,
+}
+
+impl PodManagementPolicy {
+    // parse accepts the Kubernetes policy names, ignoring case.
+    pub fn parse(value: &str) -> Option<PodManagementPolicy> {
+        match value.to_ascii_lowercase().as_str() {
+            "orderedready" => Some(PodManagementPolicy::OrderedReady),
+            "parallel" => Some(PodManagementPolicy::Parallel),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PodManagementPolicy::OrderedReady => "OrderedReady",
+            PodManagementPolicy::Parallel => "Parallel",
+        }
+    }
+}
+
+// Define the UpdateStrategy enum
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateStrategy {
+    RollingUpdate,
+    OnDelete, // Pods are only replaced when deleted by hand
+}
+
+impl UpdateStrategy {
+    // parse accepts the Kubernetes strategy names, ignoring case.
+    pub fn parse(value: &str) -> Option<UpdateStrategy> {
+        match value.to_ascii_lowercase().as_str() {
+            "rollingupdate" => Some(UpdateStrategy::RollingUpdate),
+            "ondelete" => Some(UpdateStrategy::OnDelete),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UpdateStrategy::RollingUpdate => "RollingUpdate",
+            UpdateStrategy::OnDelete => "OnDelete",
+        }
+    }
+}
+
+// Define the StatefulSetNode struct. It holds the same fields as a deployment;
+// its storage becomes a volume claim template so that every pod gets its own
+// volume.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StatefulSetNode {
+    pub deployment: DeploymentNode,
+    pub service_name: String, // Headless service that names the pods, by default the set's name
+    pub pod_management_policy: Option<PodManagementPolicy>,
+    pub update_strategy: Option<UpdateStrategy>,
+}
+
+// Implement the Node trait for StatefulSetNode
+impl Node for StatefulSetNode {
+    fn node_type(&self) -> String {
+        "StatefulSet".to_string()
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statefulset_node_type() {
+        let statefulset = StatefulSetNode {
+            deployment: DeploymentNode {
+                name: "db".to_string(),
+                ..Default::default()
+            },
+            service_name: "db".to_string(),
+            pod_management_policy: Some(PodManagementPolicy::Parallel),
+            update_strategy: None,
+        };
+        assert_eq!(statefulset.node_type(), "StatefulSet");
+        assert_eq!(statefulset.deployment.name, "db");
+    }
+
+    #[test]
+    fn test_pod_management_policy_parse() {
+        assert_eq!(
+            PodManagementPolicy::parse("Parallel"),
+            Some(PodManagementPolicy::Parallel)
+        );
+        assert_eq!(
+            PodManagementPolicy::parse("orderedready"),
+            Some(PodManagementPolicy::OrderedReady)
+        );
+        assert_eq!(PodManagementPolicy::parse("random"), None);
+        assert_eq!(PodManagementPolicy::OrderedReady.as_str(), "OrderedReady");
+    }
+
+    #[test]
+    fn test_update_strategy_parse() {
+        assert_eq!(
+            UpdateStrategy::parse("OnDelete"),
+            Some(UpdateStrategy::OnDelete)
+        );
+        assert_eq!(
+            UpdateStrategy::parse("rollingupdate"),
+            Some(UpdateStrategy::RollingUpdate)
+        );
+        assert_eq!(UpdateStrategy::parse("Recreate"), None);
+        assert_eq!(UpdateStrategy::RollingUpdate.as_str(), "RollingUpdate");
+    }
+}
@@ -0,0 +1,529 @@
+use crate::diagnostics::codes;
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::lexer::lexer::{LexerInterface, StringKind, Token};
+use crate::lexer::token::TokenType;
+use crate::lexer::variable_literals::{
+    INTERPOLATION_END, INTERPOLATION_ESCAPE, INTERPOLATION_START, LET_KEYWORD,
+};
+use crate::parser::parser::is_word;
+use std::collections::{HashMap, HashSet};
+use std::vec::IntoIter;
+
+// Variable is a `let <name> = <value>;` statement.
+struct Variable {
+    name: Token,
+    value: Token,
+}
+
+// Variables sits between the lexer and the parser. It takes the top-level
+// `let` statements out of the token stream and replaces each `${name}` in the
+// remaining double-quoted strings with the value of the variable, so the parser
+// only sees plain values. Variables are global: a script may use one before its
+// `let`. Raw strings and text blocks are kept as written, so that config
+// payloads such as shell or envsubst templates can hold a literal `${VAR}`.
+pub struct Variables {
+    tokens: IntoIter<Token>,
+    eof: Token,
+}
+
+impl Variables {
+    // resolve drains the lexer and substitutes every variable. Malformed `let`
+    // statements stop at the first error; undefined, duplicate and cyclic
+    // variables are all reported.
+    pub fn resolve(mut lexer: impl LexerInterface) -> Result<Variables, Vec<Diagnostic>> {
+        let mut tokens = Vec::new();
+        let eof = loop {
+            let token = lexer.next_token();
+            if token.token_type == TokenType::TokenEOF {
+                break token;
+            }
+            tokens.push(token);
+        };
+        let (variables, mut tokens) = take_variables(tokens, &eof).map_err(|d| vec![d])?;
+
+        let mut diagnostics = Vec::new();
+        let mut definitions: HashMap<&str, &Variable> = HashMap::new();
+        for variable in &variables {
+            match definitions.get(variable.name.value.as_str()) {
+                Some(first) => diagnostics.push(duplicate(variable, first)),
+                None => {
+                    definitions.insert(&variable.name.value, variable);
+                }
+            }
+        }
+
+        let mut resolver = Resolver {
+            definitions: &definitions,
+            resolved: HashMap::new(),
+            failed: HashSet::new(),
+            stack: Vec::new(),
+        };
+        for variable in &variables {
+            if let Err(Some(diagnostic)) = resolver.value_of(&variable.name.value, &variable.name) {
+                diagnostics.push(diagnostic);
+            }
+        }
+        for token in tokens.iter_mut().filter(|t| interpolates(t)) {
+            match interpolate(token, |name| resolver.value_of(name, token)) {
+                Ok(value) => token.value = value,
+                Err(Some(diagnostic)) => diagnostics.push(diagnostic),
+                Err(None) => {}
+            }
+        }
+
+        if !diagnostics.is_empty() {
+            return Err(diagnostics);
+        }
+        Ok(Variables {
+            tokens: tokens.into_iter(),
+            eof,
+        })
+    }
+}
+
+// Implement the LexerInterface for Variables so the parser can read from it
+impl LexerInterface for Variables {
+    fn next_token(&mut self) -> Token {
+        self.tokens.next().unwrap_or_else(|| self.eof.clone())
+    }
+}
+
+// Resolver computes variable values on demand, following references between
+// variables. Values that failed are remembered so each error is reported once.
+struct Resolver<'a> {
+    definitions: &'a HashMap<&'a str, &'a Variable>,
+    resolved: HashMap<String, String>,
+    failed: HashSet<String>,
+    stack: Vec<&'a Variable>, // Variables being resolved, outermost first
+}
+
+impl<'a> Resolver<'a> {
+    // value_of returns the value of a variable referenced from `used_at`. An
+    // error of `None` means the problem has already been reported.
+    fn value_of(&mut self, name: &str, used_at: &Token) -> Result<String, Option<Diagnostic>> {
+        if let Some(value) = self.resolved.get(name) {
+            return Ok(value.clone());
+        }
+        if self.failed.contains(name) {
+            return Err(None);
+        }
+        let Some(&variable) = self.definitions.get(name) else {
+            return Err(Some(undefined(name, used_at)));
+        };
+        if let Some(start) = self.stack.iter().position(|v| v.name.value == name) {
+            let cycle = cycle(&self.stack[start..]);
+            for v in &self.stack[start..] {
+                self.failed.insert(v.name.value.clone());
+            }
+            return Err(Some(cycle));
+        }
+
+        if !interpolates(&variable.value) {
+            self.resolved
+                .insert(name.to_string(), variable.value.value.clone());
+            return Ok(variable.value.value.clone());
+        }
+        self.stack.push(variable);
+        let result = interpolate(&variable.value, |inner| {
+            self.value_of(inner, &variable.value)
+        });
+        self.stack.pop();
+        match result {
+            Ok(value) => {
+                self.resolved.insert(name.to_string(), value.clone());
+                Ok(value)
+            }
+            Err(error) => {
+                self.failed.insert(name.to_string());
+                Err(error)
+            }
+        }
+    }
+}
+
+// take_variables splits the `let` statements off the tokens. Only statements
+// outside of blocks are variables, so `let` stays usable as a name or key.
+fn take_variables(
+    tokens: Vec<Token>,
+    eof: &Token,
+) -> Result<(Vec<Variable>, Vec<Token>), Diagnostic> {
+    let mut variables = Vec::new();
+    let mut rest = Vec::new();
+    let mut depth = 0;
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token.token_type {
+            TokenType::TokenLBrace => depth += 1,
+            TokenType::TokenRBrace => depth -= 1,
+            TokenType::TokenLet if depth == 0 => {
+                let name = expect(tokens.next(), eof, "a variable name", is_word)?;
+                if !is_variable_name(&name.value) {
+                    return Err(Diagnostic::error(
+                        codes::INVALID_VALUE,
+                        format!("invalid variable name `{}`", name.value),
+                        name.span,
+                    )
+                    .with_help("use letters, digits, `_` and `-`, starting with a letter or `_`"));
+                }
+                expect(tokens.next(), eof, "`=`", |t| *t == TokenType::TokenEquals)?;
+                let value = expect(tokens.next(), eof, "a value", |t| {
+                    *t == TokenType::TokenString || is_word(t)
+                })?;
+                if tokens
+                    .peek()
+                    .is_some_and(|t| t.token_type == TokenType::TokenSemicolon)
+                {
+                    tokens.next();
+                }
+                variables.push(Variable { name, value });
+                continue;
+            }
+            _ => {}
+        }
+        rest.push(token);
+    }
+    Ok((variables, rest))
+}
+
+// expect returns the next token of a `let` statement if it has an accepted type.
+fn expect(
+    token: Option<Token>,
+    eof: &Token,
+    expected: &str,
+    accepts: impl Fn(&TokenType) -> bool,
+) -> Result<Token, Diagnostic> {
+    let token = token.unwrap_or_else(|| eof.clone());
+    if accepts(&token.token_type) {
+        return Ok(token);
+    }
+    let found = match token.token_type {
+        TokenType::TokenEOF => "end of input".to_string(),
+        _ => format!("`{}`", token.value),
+    };
+    Err(Diagnostic::error(
+        codes::UNEXPECTED_TOKEN,
+        format!(
+            "expected {} in `{}` statement, found {}",
+            expected, LET_KEYWORD, found
+        ),
+        token.span,
+    ))
+}
+
+// is_variable_name reports whether a word can name a variable.
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// interpolates reports whether a token is a double-quoted string, the only
+// kind of value that variables are substituted in.
+fn interpolates(token: &Token) -> bool {
+    token.string_kind == Some(StringKind::Quoted)
+}
+
+// interpolate replaces every `${name}` in the value of a token using `lookup`.
+// `$${` stands for a literal `${`.
+fn interpolate<F>(token: &Token, mut lookup: F) -> Result<String, Option<Diagnostic>>
+where
+    F: FnMut(&str) -> Result<String, Option<Diagnostic>>,
+{
+    let mut value = String::new();
+    let mut rest = token.value.as_str();
+    loop {
+        let Some(start) = rest.find(INTERPOLATION_START) else {
+            value.push_str(rest);
+            return Ok(value);
+        };
+        if rest[..start].ends_with('$') && rest[start - 1..].starts_with(INTERPOLATION_ESCAPE) {
+            value.push_str(&rest[..start - 1]);
+            value.push_str(INTERPOLATION_START);
+            rest = &rest[start + INTERPOLATION_START.len()..];
+            continue;
+        }
+        value.push_str(&rest[..start]);
+        rest = &rest[start + INTERPOLATION_START.len()..];
+        let Some(end) = rest.find(INTERPOLATION_END) else {
+            return Err(Some(
+                Diagnostic::error(
+                    codes::INVALID_VALUE,
+                    format!(
+                        "unterminated `{}` on line {}",
+                        INTERPOLATION_START, token.line_number
+                    ),
+                    token.span,
+                )
+                .with_help(format!(
+                    "close it with `{}`, or write `{}` for a literal `{}`",
+                    INTERPOLATION_END, INTERPOLATION_ESCAPE, INTERPOLATION_START
+                )),
+            ));
+        };
+        value.push_str(&lookup(&rest[..end])?);
+        rest = &rest[end + INTERPOLATION_END.len()..];
+    }
+}
+
+// undefined reports a reference to a variable that is never defined.
+fn undefined(name: &str, used_at: &Token) -> Diagnostic {
+    Diagnostic::error(
+        codes::UNDEFINED_VARIABLE,
+        format!(
+            "undefined variable `{}` on line {}",
+            name, used_at.line_number
+        ),
+        used_at.span,
+    )
+    .with_help(format!(
+        "define it with `{} {} = \"...\";`",
+        LET_KEYWORD, name
+    ))
+}
+
+// duplicate reports a variable defined a second time.
+fn duplicate(variable: &Variable, first: &Variable) -> Diagnostic {
+    Diagnostic::error(
+        codes::DUPLICATE_VARIABLE,
+        format!(
+            "variable `{}` on line {} is already defined",
+            variable.name.value, variable.name.line_number
+        ),
+        variable.name.span,
+    )
+    .with_label(
+        first.name.span,
+        format!("first defined on line {}", first.name.line_number),
+    )
+    .with_help("variables cannot be shadowed; rename one of them")
+}
+
+// cycle reports variables whose values refer to each other in a loop. The
+// first variable of the cycle refers, through the others, back to itself.
+fn cycle(variables: &[&Variable]) -> Diagnostic {
+    let first = variables[0];
+    let path: Vec<&str> = variables
+        .iter()
+        .map(|v| v.name.value.as_str())
+        .chain([first.name.value.as_str()])
+        .collect();
+    let mut diagnostic = Diagnostic::error(
+        codes::VARIABLE_CYCLE,
+        format!(
+            "variable `{}` on line {} refers to itself: {}",
+            first.name.value,
+            first.name.line_number,
+            path.join(" -> ")
+        ),
+        first.name.span,
+    );
+    for v in &variables[1..] {
+        diagnostic = diagnostic.with_label(
+            v.name.span,
+            format!(
+                "`{}` is defined on line {}",
+                v.name.value, v.name.line_number
+            ),
+        );
+    }
+    diagnostic.with_help("break the loop by writing one of the values out")
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lexer::{Lexer, Span};
+
+    fn resolve(input: &str) -> Result<Vec<Token>, Vec<Diagnostic>> {
+        let mut variables = Variables::resolve(Lexer::new(input))?;
+        let mut tokens = Vec::new();
+        loop {
+            let token = variables.next_token();
+            if token.token_type == TokenType::TokenEOF {
+                return Ok(tokens);
+            }
+            tokens.push(token);
+        }
+    }
+
+    fn values(input: &str) -> Vec<String> {
+        resolve(input)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.value)
+            .collect()
+    }
+
+    fn errors(input: &str) -> Vec<Diagnostic> {
+        resolve(input).err().unwrap()
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let input = "let registry = \"ghcr.io/acme\";\nimage: \"${registry}/api:v1\";";
+        assert_eq!(
+            values(input),
+            vec!["image", ":", "ghcr.io/acme/api:v1", ";"]
+        );
+    }
+
+    #[test]
+    fn test_word_values_and_optional_semicolon() {
+        let input =
+            "let cpu = 250m\nlet replicas = 3;\nrequests: \"${cpu}\"; replicas: \"${replicas}\";";
+        assert_eq!(
+            values(input),
+            vec!["requests", ":", "250m", ";", "replicas", ":", "3", ";"]
+        );
+    }
+
+    #[test]
+    fn test_variables_refer_to_each_other() {
+        let input = "image: \"${api}\";\nlet api = \"${registry}/api:${tag}\";\nlet registry = \"ghcr.io/acme\";\nlet tag = v1;";
+        assert_eq!(
+            values(input),
+            vec!["image", ":", "ghcr.io/acme/api:v1", ";"]
+        );
+    }
+
+    #[test]
+    fn test_escaped_interpolation() {
+        let input = "let x = a;\ncommand: \"echo $${HOME} ${x} $$ $x\";";
+        assert_eq!(
+            values(input),
+            vec!["command", ":", "echo ${HOME} a $$ $x", ";"]
+        );
+    }
+
+    #[test]
+    fn test_raw_strings_and_text_blocks_are_kept() {
+        let input = "\
+let home = '${HOME}';
+a: '${HOME}/bin';
+b: \"\"\"
+    export PATH=${PATH}:${home}
+    \"\"\";
+c: \"${home}\";";
+        assert_eq!(
+            values(input),
+            vec![
+                "a",
+                ":",
+                "${HOME}/bin",
+                ";",
+                "b",
+                ":",
+                "export PATH=${PATH}:${home}\n",
+                ";",
+                "c",
+                ":",
+                "${HOME}",
+                ";"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_let_inside_block_is_a_word() {
+        let input = "deploy app {\n  labels { let: x; }\n}";
+        assert!(values(input).contains(&"let".to_string()));
+    }
+
+    #[test]
+    fn test_undefined_variable() {
+        let input = "let a = \"${missing}\";\nimage: \"${a}\";\nargs: [\"${a}\", \"${other}\"];";
+        let errors = errors(input);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].code, codes::UNDEFINED_VARIABLE);
+        assert_eq!(errors[0].message, "undefined variable `missing` on line 1");
+        assert_eq!(errors[0].span, Span::new(8, 20));
+        assert_eq!(
+            errors[0].help.as_deref(),
+            Some("define it with `let missing = \"...\";`")
+        );
+        assert_eq!(errors[1].message, "undefined variable `other` on line 3");
+    }
+
+    #[test]
+    fn test_duplicate_variable() {
+        let input = "let tag = v1;\nlet tag = v2;\nimage: \"api:${tag}\";";
+        let errors = errors(input);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, codes::DUPLICATE_VARIABLE);
+        assert_eq!(
+            errors[0].message,
+            "variable `tag` on line 2 is already defined"
+        );
+        assert_eq!(errors[0].span, Span::new(18, 21));
+        assert_eq!(errors[0].labels[0].message, "first defined on line 1");
+        assert_eq!(errors[0].labels[0].span, Span::new(4, 7));
+    }
+
+    #[test]
+    fn test_variable_cycle() {
+        let input = "let a = \"${b}\";\nlet b = \"x${c}\";\nlet c = \"${a}\";\nimage: \"${b}\";";
+        let errors = errors(input);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, codes::VARIABLE_CYCLE);
+        assert_eq!(
+            errors[0].message,
+            "variable `a` on line 1 refers to itself: a -> b -> c -> a"
+        );
+        assert_eq!(errors[0].span, Span::new(4, 5));
+        assert_eq!(errors[0].labels.len(), 2);
+        assert_eq!(errors[0].labels[1].message, "`c` is defined on line 3");
+        assert_eq!(errors[0].labels[1].span, Span::new(37, 38));
+
+        let errors = self::errors("let a = \"${a}\";");
+        assert_eq!(
+            errors[0].message,
+            "variable `a` on line 1 refers to itself: a -> a"
+        );
+    }
+
+    #[test]
+    fn test_unterminated_interpolation() {
+        let errors = errors("image: \"${registry/api\";");
+        assert_eq!(errors[0].code, codes::INVALID_VALUE);
+        assert_eq!(errors[0].message, "unterminated `${` on line 1");
+    }
+
+    #[test]
+    fn test_malformed_let() {
+        for (input, message) in [
+            (
+                "let = x;",
+                "expected a variable name in `let` statement, found `=`",
+            ),
+            ("let x: y;", "expected `=` in `let` statement, found `:`"),
+            (
+                "let x = ;",
+                "expected a value in `let` statement, found `;`",
+            ),
+            (
+                "let x =",
+                "expected a value in `let` statement, found end of input",
+            ),
+        ] {
+            let errors = errors(input);
+            assert_eq!(errors.len(), 1, "resolving {}", input);
+            assert_eq!(errors[0].code, codes::UNEXPECTED_TOKEN);
+            assert_eq!(errors[0].message, message);
+        }
+        let errors = errors("let 1x = y;");
+        assert_eq!(errors[0].code, codes::INVALID_VALUE);
+        assert_eq!(errors[0].message, "invalid variable name `1x`");
+    }
+
+    #[test]
+    fn test_is_variable_name() {
+        assert!(is_variable_name("registry"));
+        assert!(is_variable_name("_base-url2"));
+        assert!(!is_variable_name("2x"));
+        assert!(!is_variable_name("a.b"));
+        assert!(!is_variable_name(""));
+    }
+}
@@ -0,0 +1,1810 @@
+use crate::diagnostics::codes;
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::generator::ingress::backend_port;
+use crate::generator::labels::NAME_LABEL;
+use crate::generator::network::{find_app, rule_port};
+use crate::lexer::lexer::Span;
+use crate::nodes::autoscale_node::{AutoscaleNode, ResourceTarget};
+use crate::nodes::deployment_node::{
+    DeploymentNode, EnvSource, EnvValue, PortRef, ProbeAction, ResourceRequirementsNode,
+};
+use crate::nodes::disruption_node::{DisruptionBudget, DisruptionNode, DisruptionValue};
+use crate::nodes::field_spans::FieldSpans;
+use crate::nodes::ingress_node::IngressNode;
+use crate::nodes::job_node::JobNode;
+use crate::nodes::network_node::{Direction, NetworkNode, NetworkPeer};
+use crate::nodes::ordered_map::OrderedMap;
+use crate::nodes::rbac_node::{api_resource, BindingNode, RoleNode, RoleScope, VERBS};
+use crate::nodes::service_node::ServiceNode;
+use crate::nodes::statefulset_node::StatefulSetNode;
+use crate::parser::parser::Program;
+use std::collections::HashMap;
+
+// Longest name of a cronjob; the jobs it creates add a suffix to it and must
+// stay within the 63 characters of a label value.
+const MAX_CRONJOB_NAME_LENGTH: usize = 52;
+
+// validate runs the semantic checks on a parsed program and returns every
+// problem found, in source order.
+pub fn validate(program: &Program) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for (kind, deployment) in program.workloads() {
+        if deployment.image.is_empty() {
+            diagnostics.push(
+                Diagnostic::error(
+                    codes::MISSING_IMAGE,
+                    format!("{} `{}` has no image", kind, deployment.name),
+                    deployment.span,
+                )
+                .with_help("add `image: \"<repository>:<tag>\";`"),
+            );
+        }
+        check_deployment(kind, deployment, &mut diagnostics);
+        check_env_references(kind, deployment, program, &mut diagnostics);
+        if let Some(autoscale) = &deployment.autoscale {
+            check_autoscale(kind, deployment, autoscale, &mut diagnostics);
+        }
+        if let Some(disruption) = &deployment.disruption {
+            check_disruption(kind, deployment, disruption, &mut diagnostics);
+        }
+        if let Some(network) = &deployment.network {
+            check_network(kind, deployment, network, program, &mut diagnostics);
+        }
+    }
+
+    for statefulset in &program.statefulsets {
+        check_statefulset(statefulset, program, &mut diagnostics);
+    }
+
+    for job in &program.jobs {
+        check_job("job", job, &mut diagnostics);
+    }
+
+    for cronjob in &program.cronjobs {
+        check_job("cronjob", &cronjob.job, &mut diagnostics);
+        let deployment = &cronjob.job.deployment;
+        if deployment.name.len() > MAX_CRONJOB_NAME_LENGTH {
+            diagnostics.push(
+                Diagnostic::error(
+                    codes::INVALID_NAME,
+                    format!(
+                        "cronjob name `{}` is longer than {} characters",
+                        deployment.name, MAX_CRONJOB_NAME_LENGTH
+                    ),
+                    deployment.span,
+                )
+                .with_help("the jobs it creates add an 11-character suffix to its name"),
+            );
+        }
+    }
+
+    for (kind, name, namespace, labels, annotations, data, fields, span) in program
+        .configs
+        .iter()
+        .map(|c| {
+            (
+                "config",
+                &c.name,
+                &c.namespace,
+                &c.labels,
+                &c.annotations,
+                &c.data,
+                &c.fields,
+                c.span,
+            )
+        })
+        .chain(program.secrets.iter().map(|s| {
+            (
+                "secret",
+                &s.name,
+                &s.namespace,
+                &s.labels,
+                &s.annotations,
+                &s.data,
+                &s.fields,
+                s.span,
+            )
+        }))
+    {
+        check_label(&format!("{} name", kind), name, span, &mut diagnostics);
+        let namespace_span = fields.get("namespace", span);
+        check_label("namespace", namespace, namespace_span, &mut diagnostics);
+        check_metadata(labels, annotations, fields, span, &mut diagnostics);
+        let mut keys: Vec<&String> = data.keys().collect();
+        keys.sort();
+        for key in keys {
+            if !is_config_key(key) {
+                diagnostics.push(
+                    Diagnostic::error(
+                        codes::INVALID_NAME,
+                        format!("{} key `{}` is not valid", kind, key),
+                        fields.get(&format!("data.{}", key), span),
+                    )
+                    .with_help("use letters, digits, `-`, `_` and `.`"),
+                );
+            }
+        }
+    }
+
+    for service in &program.services {
+        check_service(service, &mut diagnostics);
+        if service.app.is_some()
+            && !program
+                .workloads()
+                .any(|(_, d)| d.name == service.target() && d.namespace == service.namespace)
+        {
+            diagnostics.push(
+                Diagnostic::warning(
+                    codes::UNKNOWN_REFERENCE,
+                    format!(
+                        "service `{}` targets app `{}`, which is not deployed in namespace `{}`",
+                        service.name,
+                        service.target(),
+                        service.namespace
+                    ),
+                    service.fields.get("app", service.span),
+                )
+                .with_help("the service selects no pods unless the app is deployed elsewhere"),
+            );
+        }
+    }
+
+    for ingress in &program.ingresses {
+        check_ingress(ingress, program, &mut diagnostics);
+    }
+
+    let deployments = program
+        .deployments
+        .iter()
+        .map(|d| (d.name.as_str(), d.namespace.as_str(), d.span));
+    check_duplicates("deployment", deployments, &mut diagnostics);
+
+    let statefulsets = program.statefulsets.iter().map(|s| {
+        (
+            s.deployment.name.as_str(),
+            s.deployment.namespace.as_str(),
+            s.deployment.span,
+        )
+    });
+    check_duplicates("statefulset", statefulsets, &mut diagnostics);
+
+    let jobs = program.jobs.iter().map(|j| {
+        (
+            j.deployment.name.as_str(),
+            j.deployment.namespace.as_str(),
+            j.deployment.span,
+        )
+    });
+    check_duplicates("job", jobs, &mut diagnostics);
+
+    let cronjobs = program.cronjobs.iter().map(|c| {
+        (
+            c.job.deployment.name.as_str(),
+            c.job.deployment.namespace.as_str(),
+            c.job.deployment.span,
+        )
+    });
+    check_duplicates("cronjob", cronjobs, &mut diagnostics);
+
+    let services = program
+        .services
+        .iter()
+        .map(|s| (s.name.as_str(), s.namespace.as_str(), s.span));
+    check_duplicates("service", services, &mut diagnostics);
+
+    let ingresses = program
+        .ingresses
+        .iter()
+        .map(|i| (i.name.as_str(), i.namespace.as_str(), i.span));
+    check_duplicates("ingress", ingresses, &mut diagnostics);
+
+    let configs = program
+        .configs
+        .iter()
+        .map(|c| (c.name.as_str(), c.namespace.as_str(), c.span));
+    check_duplicates("config", configs, &mut diagnostics);
+
+    let secrets = program
+        .secrets
+        .iter()
+        .map(|s| (s.name.as_str(), s.namespace.as_str(), s.span));
+    check_duplicates("secret", secrets, &mut diagnostics);
+
+    for role in &program.roles {
+        check_role(role, &mut diagnostics);
+    }
+    for binding in &program.bindings {
+        check_binding(binding, program, &mut diagnostics);
+    }
+    let roles = program
+        .roles
+        .iter()
+        .filter(|r| r.scope == RoleScope::Namespace)
+        .map(|r| (r.name.as_str(), r.namespace.as_str(), r.span));
+    check_duplicates("role", roles, &mut diagnostics);
+    let cluster_roles = program
+        .roles
+        .iter()
+        .filter(|r| r.scope == RoleScope::Cluster)
+        .map(|r| (r.name.as_str(), "", r.span));
+    check_duplicates("cluster role", cluster_roles, &mut diagnostics);
+
+    diagnostics.sort_by_key(|d| d.span.start);
+    diagnostics
+}
+
+// check_deployment reports invalid names, counts, ports, env keys and resources
+// of a workload of the given kind.
+fn check_deployment(kind: &str, deployment: &DeploymentNode, diagnostics: &mut Vec<Diagnostic>) {
+    let span = deployment.span;
+    let fields = &deployment.fields;
+    check_label(
+        &format!("{} name", kind),
+        &deployment.name,
+        span,
+        diagnostics,
+    );
+    check_label(
+        "namespace",
+        &deployment.namespace,
+        fields.get("namespace", span),
+        diagnostics,
+    );
+    check_metadata(
+        &deployment.labels,
+        &deployment.annotations,
+        fields,
+        span,
+        diagnostics,
+    );
+
+    if let Some(service_account) = &deployment.service_account {
+        check_label(
+            "service account name",
+            service_account,
+            fields.get("serviceAccount", span),
+            diagnostics,
+        );
+    }
+
+    if deployment.replicas < 0 {
+        diagnostics.push(Diagnostic::error(
+            codes::OUT_OF_RANGE,
+            format!(
+                "{} `{}` has a negative replica count ({})",
+                kind, deployment.name, deployment.replicas
+            ),
+            fields.get("replicas", span),
+        ));
+    }
+
+    let mut ports: Vec<(&String, &i32)> = deployment.ports.iter().collect();
+    ports.sort();
+    for (name, port) in ports {
+        let port_span = fields.get(&format!("ports.{}", name), span);
+        if !is_port_name(name) {
+            diagnostics.push(
+                Diagnostic::error(
+                    codes::INVALID_NAME,
+                    format!("port name `{}` is not valid", name),
+                    port_span,
+                )
+                .with_help("port names have at most 15 lowercase letters, digits and `-`, with at least one letter"),
+            );
+        }
+        check_port(&format!("port `{}`", name), *port, port_span, diagnostics);
+    }
+    check_repeated_ports(
+        &format!("{} `{}`", kind, deployment.name),
+        fields,
+        diagnostics,
+    );
+
+    let mut keys: Vec<&String> = deployment.env.keys().collect();
+    keys.sort();
+    for key in keys {
+        if !is_env_key(key) {
+            diagnostics.push(
+                Diagnostic::error(
+                    codes::INVALID_NAME,
+                    format!("environment variable name `{}` is not valid", key),
+                    fields.get(&format!("env.{}", key), span),
+                )
+                .with_help("use letters, digits, `_`, `-` and `.`, not starting with a digit"),
+            );
+        }
+    }
+
+    if let Some(resources) = &deployment.resources {
+        check_resources(resources, fields, span, diagnostics);
+    }
+
+    if let Some(storage) = &deployment.storage {
+        check_label(
+            "volume name",
+            &storage.volume,
+            fields.get("storage.volume", span),
+            diagnostics,
+        );
+    }
+
+    check_probes(kind, deployment, diagnostics);
+}
+
+// check_env_references reports env values and sources that refer to configs and
+// secrets. A reference to a config or secret that the script does not define is
+// only a warning, as it may be created outside of it; a key missing from one
+// that the script defines is an error.
+fn check_env_references(
+    workload: &str,
+    deployment: &DeploymentNode,
+    program: &Program,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let find = |kind: &str, name: &str| -> Option<&OrderedMap<String, String>> {
+        match kind {
+            "config" => program
+                .configs
+                .iter()
+                .find(|c| c.name == name && c.namespace == deployment.namespace)
+                .map(|c| &c.data),
+            _ => program
+                .secrets
+                .iter()
+                .find(|s| s.name == name && s.namespace == deployment.namespace)
+                .map(|s| &s.data),
+        }
+    };
+
+    let mut references: Vec<(&str, &String, Option<&String>, String)> = Vec::new();
+    let mut env: Vec<(&String, &EnvValue)> = deployment.env.iter().collect();
+    env.sort_by_key(|(key, _)| *key);
+    for (variable, value) in env {
+        let path = format!("env.{}", variable);
+        match value {
+            EnvValue::ConfigKey { config, key } => {
+                references.push(("config", config, Some(key), path))
+            }
+            EnvValue::SecretKey { secret, key } => {
+                references.push(("secret", secret, Some(key), path))
+            }
+            EnvValue::Value(_) => {}
+        }
+    }
+    for source in &deployment.env_from {
+        let (kind, name) = match source {
+            EnvSource::Config(name) => ("config", name),
+            EnvSource::Secret(name) => ("secret", name),
+        };
+        references.push((kind, name, None, format!("envFrom.{}.{}", kind, name)));
+    }
+
+    for (kind, name, key, path) in references {
+        let span = deployment.fields.get(&path, deployment.span);
+        match (find(kind, name), key) {
+            (None, _) => diagnostics.push(
+                Diagnostic::warning(
+                    codes::UNKNOWN_REFERENCE,
+                    format!(
+                        "{} `{}` refers to {} `{}`, which is not defined in namespace `{}`",
+                        workload, deployment.name, kind, name, deployment.namespace
+                    ),
+                    span,
+                )
+                .with_help(format!("the {} must exist before the pods start", kind)),
+            ),
+            (Some(data), Some(key)) if !data.contains_key(key) => {
+                diagnostics.push(Diagnostic::error(
+                    codes::UNKNOWN_REFERENCE,
+                    format!("{} `{}` has no key `{}`", kind, name, key),
+                    span,
+                ))
+            }
+            _ => {}
+        }
+    }
+}
+
+// check_probes reports probes whose port is not declared or out of range, and
+// timings Kubernetes rejects. Problems point at the probe keyword.
+fn check_probes(workload: &str, deployment: &DeploymentNode, diagnostics: &mut Vec<Diagnostic>) {
+    for (kind, probe) in deployment.probes.iter() {
+        let port = match &probe.action {
+            ProbeAction::Http { port, .. }
+            | ProbeAction::Tcp { port }
+            | ProbeAction::Grpc { port, .. } => Some(port),
+            ProbeAction::Exec { .. } => None,
+        };
+        match port {
+            Some(PortRef::Name(name)) if !deployment.ports.contains_key(name) => {
+                diagnostics.push(
+                    Diagnostic::error(
+                        codes::UNKNOWN_PORT,
+                        format!("{} probe refers to unknown port `{}`", kind, name),
+                        probe.span,
+                    )
+                    .with_help(format!(
+                        "declare it in the `ports` block of {} `{}`",
+                        workload, deployment.name
+                    )),
+                );
+            }
+            Some(PortRef::Number(number)) => check_port(
+                &format!("{} probe port", kind),
+                *number,
+                probe.span,
+                diagnostics,
+            ),
+            _ => {}
+        }
+
+        for (field, value) in [
+            ("period", probe.period),
+            ("timeout", probe.timeout),
+            ("failureThreshold", probe.failure_threshold),
+            ("successThreshold", probe.success_threshold),
+        ] {
+            if value.is_some_and(|value| value < 1) {
+                diagnostics.push(Diagnostic::error(
+                    codes::OUT_OF_RANGE,
+                    format!("{} probe `{}` must be at least 1", kind, field),
+                    probe.span,
+                ));
+            }
+        }
+        if kind != "readiness" && probe.success_threshold.is_some_and(|value| value != 1) {
+            diagnostics.push(Diagnostic::error(
+                codes::OUT_OF_RANGE,
+                format!("{} probe `successThreshold` must be 1", kind),
+                probe.span,
+            ));
+        }
+    }
+}
+
+// check_statefulset reports an invalid service name, and a governing service
+// that the script does not define. The pods only get stable network names once
+// that service exists, so a missing one is a warning.
+fn check_statefulset(
+    statefulset: &StatefulSetNode,
+    program: &Program,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let deployment = &statefulset.deployment;
+    let span = deployment.fields.get("serviceName", deployment.span);
+    check_label("service name", &statefulset.service_name, span, diagnostics);
+    if !program
+        .services
+        .iter()
+        .any(|s| s.name == statefulset.service_name && s.namespace == deployment.namespace)
+    {
+        diagnostics.push(
+            Diagnostic::warning(
+                codes::UNKNOWN_REFERENCE,
+                format!(
+                    "statefulset `{}` uses service `{}`, which is not defined in namespace `{}`",
+                    deployment.name, statefulset.service_name, deployment.namespace
+                ),
+                span,
+            )
+            .with_help(format!(
+                "add a `service {} {{ type: headless; }}` block",
+                statefulset.service_name
+            )),
+        );
+    }
+}
+
+// check_job reports job settings that Kubernetes rejects.
+fn check_job(kind: &str, job: &JobNode, diagnostics: &mut Vec<Diagnostic>) {
+    for (field, value, min) in [
+        ("backoffLimit", job.backoff_limit, 0),
+        ("completions", job.completions, 0),
+        ("parallelism", job.parallelism, 0),
+        ("activeDeadlineSeconds", job.active_deadline_seconds, 1),
+    ] {
+        if value.is_some_and(|value| value < min) {
+            diagnostics.push(Diagnostic::error(
+                codes::OUT_OF_RANGE,
+                format!(
+                    "{} `{}` `{}` must be at least {}",
+                    kind, job.deployment.name, field, min
+                ),
+                job.deployment.fields.get(field, job.deployment.span),
+            ));
+        }
+    }
+}
+
+// check_autoscale reports replica bounds that Kubernetes rejects, and
+// utilization targets for a resource the containers do not request. The
+// autoscaler measures utilization against the request, so without one it never
+// scales; that is a warning.
+fn check_autoscale(
+    kind: &str,
+    deployment: &DeploymentNode,
+    autoscale: &AutoscaleNode,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let span = autoscale.span;
+    for (field, value) in [("min", autoscale.min), ("max", Some(autoscale.max))] {
+        if value.is_some_and(|value| value < 1) {
+            diagnostics.push(Diagnostic::error(
+                codes::OUT_OF_RANGE,
+                format!(
+                    "{} `{}` autoscale `{}` must be at least 1",
+                    kind, deployment.name, field
+                ),
+                span,
+            ));
+        }
+    }
+    if let Some(min) = autoscale.min.filter(|min| *min > autoscale.max) {
+        diagnostics.push(Diagnostic::error(
+            codes::OUT_OF_RANGE,
+            format!(
+                "{} `{}` autoscale `min` ({}) is greater than `max` ({})",
+                kind, deployment.name, min, autoscale.max
+            ),
+            span,
+        ));
+    }
+
+    let requests = deployment.resources.as_ref().map(|r| &r.requests);
+    for (resource, target) in autoscale.resources() {
+        let ResourceTarget::Utilization(percent) = target else {
+            continue;
+        };
+        if *percent < 1 {
+            diagnostics.push(Diagnostic::error(
+                codes::OUT_OF_RANGE,
+                format!(
+                    "{} `{}` autoscale {} utilization must be at least 1%",
+                    kind, deployment.name, resource
+                ),
+                span,
+            ));
+        }
+        let requested = match resource {
+            "cpu" => requests.is_some_and(|r| r.cpu.is_some()),
+            _ => requests.is_some_and(|r| r.memory.is_some()),
+        };
+        if !requested {
+            diagnostics.push(
+                Diagnostic::warning(
+                    codes::MISSING_REQUEST,
+                    format!(
+                        "{} `{}` autoscales on {} utilization but does not request {}",
+                        kind, deployment.name, resource, resource
+                    ),
+                    span,
+                )
+                .with_help(format!(
+                    "add `requests {{ {}: ...; }}` to its resources, or target an average value",
+                    resource
+                )),
+            );
+        }
+    }
+}
+
+// check_disruption reports a budget bound outside its range, and a budget that
+// never allows an eviction. Node drains then wait forever, so cluster upgrades
+// hang; that is a warning. An autoscaled workload is checked against its
+// minimum replica count.
+fn check_disruption(
+    kind: &str,
+    deployment: &DeploymentNode,
+    disruption: &DisruptionNode,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let span = disruption.span;
+    let field = disruption.budget.field();
+    let value = disruption.budget.value();
+    let in_range = match value {
+        DisruptionValue::Count(count) => count >= 0,
+        DisruptionValue::Percent(percent) => (0..=100).contains(&percent),
+    };
+    if !in_range {
+        diagnostics.push(Diagnostic::error(
+            codes::OUT_OF_RANGE,
+            format!(
+                "{} `{}` disruption `{}` ({}) must be a count of at least 0 or a percentage up to 100%",
+                kind, deployment.name, field, value
+            ),
+            span,
+        ));
+        return;
+    }
+
+    let replicas = match &deployment.autoscale {
+        Some(autoscale) => autoscale.min.unwrap_or(1),
+        None => deployment.replicas,
+    };
+    let blocked = match disruption.budget {
+        DisruptionBudget::MinAvailable(DisruptionValue::Count(count)) => count >= replicas,
+        DisruptionBudget::MinAvailable(DisruptionValue::Percent(percent)) => percent == 100,
+        DisruptionBudget::MaxUnavailable(value) => {
+            matches!(
+                value,
+                DisruptionValue::Count(0) | DisruptionValue::Percent(0)
+            )
+        }
+    };
+    if blocked {
+        diagnostics.push(
+            Diagnostic::warning(
+                codes::BLOCKED_EVICTION,
+                format!(
+                    "{} `{}` disruption budget `{}: {}` never allows an eviction with {} replicas",
+                    kind, deployment.name, field, value, replicas
+                ),
+                span,
+            )
+            .with_help(
+                "node drains wait on it forever; keep `minAvailable` below the replica count",
+            ),
+        );
+    }
+}
+
+// check_network reports network rules that refer to an app the script does not
+// define, and ports that do not resolve. Ingress ports are looked up on the
+// workload itself, egress ports on the apps the traffic goes to. Problems point
+// at the `allow` of the rule.
+fn check_network(
+    kind: &str,
+    deployment: &DeploymentNode,
+    network: &NetworkNode,
+    program: &Program,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let namespace = &deployment.namespace;
+    for rule in &network.rules {
+        let mut apps = Vec::new();
+        for peer in &rule.peers {
+            let NetworkPeer::App(app) = peer else {
+                continue;
+            };
+            match find_app(program, namespace, app) {
+                Some(workload) => apps.push(workload),
+                None => diagnostics.push(Diagnostic::error(
+                    codes::UNKNOWN_REFERENCE,
+                    format!(
+                        "network rule of {} `{}` refers to app `{}`, which is not defined in namespace `{}`",
+                        kind, deployment.name, app, namespace
+                    ),
+                    rule.span,
+                )),
+            }
+        }
+
+        for port in &rule.ports {
+            let name = match port {
+                PortRef::Number(number) => {
+                    check_port("network rule port", *number, rule.span, diagnostics);
+                    continue;
+                }
+                PortRef::Name(_) if rule_port(program, deployment, rule, port).is_some() => {
+                    continue
+                }
+                PortRef::Name(name) => name,
+            };
+            if rule.direction == Direction::Ingress {
+                diagnostics.push(
+                    Diagnostic::error(
+                        codes::UNKNOWN_PORT,
+                        format!("{} `{}` has no port `{}`", kind, deployment.name, name),
+                        rule.span,
+                    )
+                    .with_help(format!(
+                        "declare it in the `ports` block of {} `{}`",
+                        kind, deployment.name
+                    )),
+                );
+            } else if rule
+                .peers
+                .iter()
+                .any(|peer| matches!(peer, NetworkPeer::Cidr(_)))
+            {
+                diagnostics.push(
+                    Diagnostic::error(
+                        codes::UNKNOWN_PORT,
+                        format!("port `{}` cannot be resolved for a `cidr(...)` peer", name),
+                        rule.span,
+                    )
+                    .with_help("use a port number for traffic to an address range"),
+                );
+            } else {
+                for app in apps.iter().filter(|app| !app.ports.contains_key(name)) {
+                    diagnostics.push(
+                        Diagnostic::error(
+                            codes::UNKNOWN_PORT,
+                            format!("app `{}` has no port `{}`", app.name, name),
+                            rule.span,
+                        )
+                        .with_help(format!(
+                            "declare it in the `ports` block of `{}`, or use a port number",
+                            app.name
+                        )),
+                    );
+                }
+            }
+        }
+    }
+}
+
+// check_role reports invalid names, and rules for resources or verbs that the
+// built-in API table does not list. A namespaced role cannot grant access to a
+// cluster-scoped resource; Kubernetes accepts the rule but it has no effect,
+// so that is a warning. Problems with a rule point at its resource.
+fn check_role(role: &RoleNode, diagnostics: &mut Vec<Diagnostic>) {
+    let span = role.span;
+    check_label("role name", &role.name, span, diagnostics);
+    if role.scope == RoleScope::Namespace {
+        let namespace_span = role.fields.get("namespace", span);
+        check_label("namespace", &role.namespace, namespace_span, diagnostics);
+    }
+    check_metadata(
+        &role.labels,
+        &role.annotations,
+        &role.fields,
+        span,
+        diagnostics,
+    );
+
+    for (name, rule) in role.rules.iter() {
+        match api_resource(name) {
+            None => diagnostics.push(
+                Diagnostic::error(
+                    codes::UNKNOWN_RESOURCE,
+                    format!("role `{}` refers to unknown resource `{}`", role.name, name),
+                    rule.span,
+                )
+                .with_help(
+                    "write the plural, lowercase name, such as `pods`, `deployments` or `pods/log`",
+                ),
+            ),
+            Some(resource) if !resource.namespaced && role.scope == RoleScope::Namespace => {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        codes::UNKNOWN_RESOURCE,
+                        format!(
+                            "role `{}` cannot grant cluster-scoped resource `{}`",
+                            role.name, name
+                        ),
+                        rule.span,
+                    )
+                    .with_help("add `scope: cluster;` to make it a cluster role"),
+                )
+            }
+            Some(_) => {}
+        }
+        for verb in rule
+            .verbs
+            .iter()
+            .filter(|verb| !VERBS.contains(&verb.as_str()))
+        {
+            diagnostics.push(
+                Diagnostic::error(
+                    codes::UNKNOWN_RESOURCE,
+                    format!(
+                        "role `{}` grants unknown verb `{}` on `{}`",
+                        role.name, verb, name
+                    ),
+                    rule.span,
+                )
+                .with_help(format!("use one of {}", VERBS.join(", "))),
+            );
+        }
+    }
+}
+
+// check_binding reports a binding to a role that the script does not define,
+// and a service account that no workload runs as. The account may belong to
+// something outside the script, so the latter is a warning.
+fn check_binding(binding: &BindingNode, program: &Program, diagnostics: &mut Vec<Diagnostic>) {
+    let span = binding.span;
+    check_label(
+        "service account name",
+        &binding.service_account,
+        span,
+        diagnostics,
+    );
+    // A role defined twice is reported as a duplicate; check the binding once.
+    let mut roles: Vec<&RoleNode> = Vec::new();
+    for role in program.roles.iter().filter(|r| r.name == binding.role) {
+        if !roles
+            .iter()
+            .any(|r| r.scope == role.scope && r.namespace == role.namespace)
+        {
+            roles.push(role);
+        }
+    }
+    if roles.is_empty() {
+        diagnostics.push(Diagnostic::error(
+            codes::UNKNOWN_REFERENCE,
+            format!(
+                "binding refers to role `{}`, which is not defined",
+                binding.role
+            ),
+            span,
+        ));
+    }
+
+    for role in roles {
+        let used = program.workloads().any(|(_, workload)| {
+            workload.service_account.as_deref() == Some(binding.service_account.as_str())
+                && (role.scope == RoleScope::Cluster || workload.namespace == role.namespace)
+        });
+        if used {
+            continue;
+        }
+        let message = match role.scope {
+            RoleScope::Namespace => format!(
+                "role `{}` is bound to service account `{}`, which no workload in namespace `{}` runs as",
+                role.name, binding.service_account, role.namespace
+            ),
+            RoleScope::Cluster => format!(
+                "cluster role `{}` is bound to service account `{}`, which no workload runs as",
+                role.name, binding.service_account
+            ),
+        };
+        diagnostics.push(
+            Diagnostic::warning(codes::UNKNOWN_REFERENCE, message, span).with_help(format!(
+                "add `serviceAccount: {};` to the workload that needs the role",
+                binding.service_account
+            )),
+        );
+    }
+}
+
+// check_ingress reports an invalid name or host, and routes to a service or
+// port that the script does not define. Problems with a route point at its
+// `service(...)` reference.
+fn check_ingress(ingress: &IngressNode, program: &Program, diagnostics: &mut Vec<Diagnostic>) {
+    let span = ingress.span;
+    check_label("ingress name", &ingress.name, span, diagnostics);
+    let fields = &ingress.fields;
+    check_label(
+        "namespace",
+        &ingress.namespace,
+        fields.get("namespace", span),
+        diagnostics,
+    );
+    check_metadata(
+        &ingress.labels,
+        &ingress.annotations,
+        fields,
+        span,
+        diagnostics,
+    );
+
+    if let Some(host) = &ingress.host {
+        if !is_dns_subdomain(host.strip_prefix("*.").unwrap_or(host)) {
+            diagnostics.push(
+                Diagnostic::error(
+                    codes::INVALID_NAME,
+                    format!("ingress host `{}` is not a valid DNS name", host),
+                    fields.get("host", span),
+                )
+                .with_help(
+                    "use lowercase DNS labels separated by `.`, optionally starting with `*.`",
+                ),
+            );
+        }
+    }
+
+    for (path, backend) in ingress.routes.iter() {
+        let service = program
+            .services
+            .iter()
+            .find(|s| s.name == backend.service && s.namespace == ingress.namespace);
+        let Some(service) = service else {
+            diagnostics.push(Diagnostic::error(
+                codes::UNKNOWN_REFERENCE,
+                format!(
+                    "route `{}` refers to service `{}`, which is not defined in namespace `{}`",
+                    path, backend.service, ingress.namespace
+                ),
+                backend.span,
+            ));
+            continue;
+        };
+        if backend_port(program, &ingress.namespace, backend).is_some() {
+            continue;
+        }
+        let (port, help) = match &backend.port {
+            PortRef::Number(number) => (
+                number.to_string(),
+                format!(
+                    "service `{}` exposes port {}",
+                    service.name,
+                    service
+                        .ports
+                        .keys()
+                        .map(|port| port.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            ),
+            PortRef::Name(name) => (
+                name.clone(),
+                format!(
+                    "name a container port of app `{}` that the service forwards to",
+                    service.target()
+                ),
+            ),
+        };
+        diagnostics.push(
+            Diagnostic::error(
+                codes::UNKNOWN_PORT,
+                format!("service `{}` has no port `{}`", service.name, port),
+                backend.span,
+            )
+            .with_help(help),
+        );
+    }
+}
+
+// check_service reports invalid names and ports of a service.
+fn check_service(service: &ServiceNode, diagnostics: &mut Vec<Diagnostic>) {
+    let span = service.span;
+    check_label("service name", &service.name, span, diagnostics);
+    let fields = &service.fields;
+    check_label(
+        "namespace",
+        &service.namespace,
+        fields.get("namespace", span),
+        diagnostics,
+    );
+    check_metadata(
+        &service.labels,
+        &service.annotations,
+        fields,
+        span,
+        diagnostics,
+    );
+
+    let mut ports: Vec<(&i32, &i32)> = service.ports.iter().collect();
+    ports.sort();
+    for (port, target) in ports {
+        let port_span = fields.get(&format!("ports.{}", port), span);
+        let target_span = fields.get(&format!("ports.{}.targetPort", port), port_span);
+        check_port("service port", *port, port_span, diagnostics);
+        check_port("target port", *target, target_span, diagnostics);
+    }
+    check_repeated_ports(&format!("service `{}`", service.name), fields, diagnostics);
+}
+
+// check_repeated_ports reports a port written more than once in a block, which
+// would otherwise silently keep only its last value. Each repeat points at
+// itself with a label on the first one.
+fn check_repeated_ports(owner: &str, fields: &FieldSpans, diagnostics: &mut Vec<Diagnostic>) {
+    for (path, spans) in fields.repeated() {
+        let Some(port) = path.strip_prefix("ports.").filter(|p| !p.contains('.')) else {
+            continue;
+        };
+        for span in &spans[1..] {
+            diagnostics.push(
+                Diagnostic::error(
+                    codes::DUPLICATE_PORT,
+                    format!("{} defines port `{}` more than once", owner, port),
+                    *span,
+                )
+                .with_label(spans[0], "first defined here"),
+            );
+        }
+    }
+}
+
+// check_metadata reports label and annotation keys and label values that
+// Kubernetes rejects, and labels that would change the generated selector.
+// Problems point at the entry, or at `span` for one that is not written out.
+fn check_metadata(
+    labels: &OrderedMap<String, String>,
+    annotations: &OrderedMap<String, String>,
+    fields: &FieldSpans,
+    span: Span,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut labels: Vec<(&String, &String)> = labels.iter().collect();
+    labels.sort();
+    for (key, value) in labels {
+        let span = fields.get(&format!("labels.{}", key), span);
+        if key == NAME_LABEL {
+            diagnostics.push(
+                Diagnostic::error(
+                    codes::INVALID_NAME,
+                    format!("label `{}` is generated and cannot be set", key),
+                    span,
+                )
+                .with_help("it holds the app name and selects the app's pods"),
+            );
+        } else if !is_qualified_name(key) {
+            diagnostics.push(
+                Diagnostic::error(
+                    codes::INVALID_NAME,
+                    format!("label key `{}` is not valid", key),
+                    span,
+                )
+                .with_help("use an optional DNS prefix and `/`, then up to 63 letters, digits, `-`, `_` and `.`"),
+            );
+        }
+        if !is_label_value(value) {
+            diagnostics.push(
+                Diagnostic::error(
+                    codes::INVALID_NAME,
+                    format!("value `{}` of label `{}` is not valid", value, key),
+                    span,
+                )
+                .with_help("label values have at most 63 letters, digits, `-`, `_` and `.`, starting and ending with a letter or digit"),
+            );
+        }
+    }
+
+    let mut keys: Vec<&String> = annotations.keys().collect();
+    keys.sort();
+    for key in keys {
+        if !is_qualified_name(key) {
+            diagnostics.push(Diagnostic::error(
+                codes::INVALID_NAME,
+                format!("annotation key `{}` is not valid", key),
+                fields.get(&format!("annotations.{}", key), span),
+            ));
+        }
+    }
+}
+
+// check_resources reports requests that are larger than the matching limit,
+// comparing the quantities by value. Problems point at the request.
+fn check_resources(
+    resources: &ResourceRequirementsNode,
+    fields: &FieldSpans,
+    span: Span,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (resource, request, limit) in [
+        (
+            "memory",
+            &resources.requests.memory,
+            &resources.limits.memory,
+        ),
+        ("cpu", &resources.requests.cpu, &resources.limits.cpu),
+    ] {
+        if let (Some(request), Some(limit)) = (request, limit) {
+            if request > limit {
+                diagnostics.push(Diagnostic::error(
+                    codes::REQUEST_EXCEEDS_LIMIT,
+                    format!(
+                        "{} request `{}` exceeds the limit `{}`",
+                        resource, request, limit
+                    ),
+                    fields.get(&format!("resources.requests.{}", resource), span),
+                ));
+            }
+        }
+    }
+}
+
+fn check_label(what: &str, value: &str, span: Span, diagnostics: &mut Vec<Diagnostic>) {
+    if !is_dns_label(value) {
+        diagnostics.push(
+            Diagnostic::error(
+                codes::INVALID_NAME,
+                format!("{} `{}` is not a valid DNS-1123 label", what, value),
+                span,
+            )
+            .with_help("use at most 63 lowercase letters, digits and `-`, starting and ending with a letter or digit"),
+        );
+    }
+}
+
+fn check_port(what: &str, port: i32, span: Span, diagnostics: &mut Vec<Diagnostic>) {
+    if !(1..=65535).contains(&port) {
+        diagnostics.push(Diagnostic::error(
+            codes::OUT_OF_RANGE,
+            format!("{} {} is outside the range 1-65535", what, port),
+            span,
+        ));
+    }
+}
+
+// is_dns_label reports whether a name is a DNS-1123 label, the format Kubernetes
+// requires for most object names.
+pub fn is_dns_label(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    !bytes.is_empty()
+        && bytes.len() <= 63
+        && bytes
+            .iter()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'-')
+        && bytes[0] != b'-'
+        && bytes[bytes.len() - 1] != b'-'
+}
+
+// is_dns_subdomain reports whether a value is a valid DNS-1123 subdomain, such
+// as a host name: DNS labels separated by dots, at most 253 characters long.
+pub fn is_dns_subdomain(value: &str) -> bool {
+    value.len() <= 253 && value.split('.').all(is_dns_label)
+}
+
+// is_port_name reports whether a name is a valid container port name (IANA_SVC_NAME).
+pub fn is_port_name(value: &str) -> bool {
+    value.len() <= 15
+        && is_dns_label(value)
+        && !value.contains("--")
+        && value.bytes().any(|b| b.is_ascii_lowercase())
+}
+
+// is_label_value reports whether a value can be used as a label value. Empty
+// values are allowed.
+pub fn is_label_value(value: &str) -> bool {
+    value.is_empty()
+        || (value.len() <= 63
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            && value.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && value.ends_with(|c: char| c.is_ascii_alphanumeric()))
+}
+
+// is_qualified_name reports whether a key is a label or annotation key: a name
+// with an optional DNS subdomain prefix, as in `example.com/owner`.
+pub fn is_qualified_name(value: &str) -> bool {
+    let (prefix, name) = match value.split_once('/') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, value),
+    };
+    let prefix_valid =
+        prefix.is_none_or(|prefix| prefix.len() <= 253 && prefix.split('.').all(is_dns_label));
+    prefix_valid && !name.is_empty() && is_label_value(name)
+}
+
+// is_config_key reports whether a key can be used in a ConfigMap or Secret.
+pub fn is_config_key(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 253
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+// is_env_key reports whether a name can be used as an environment variable.
+pub fn is_env_key(value: &str) -> bool {
+    let mut chars = value.chars();
+    let valid = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.');
+    match chars.next() {
+        Some(first) => valid(first) && !first.is_ascii_digit() && chars.all(valid),
+        None => false,
+    }
+}
+
+// check_duplicates reports resources of one kind that reuse a name in the same
+// namespace. Cluster-wide resources are given an empty namespace.
+fn check_duplicates<'a>(
+    kind: &str,
+    resources: impl Iterator<Item = (&'a str, &'a str, Span)>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut seen: HashMap<(&str, &str), Span> = HashMap::new();
+    for (name, namespace, span) in resources {
+        let message = match namespace {
+            "" => format!("{} `{}` is defined more than once", kind, name),
+            _ => format!(
+                "{} `{}` is defined more than once in namespace `{}`",
+                kind, name, namespace
+            ),
+        };
+        match seen.get(&(name, namespace)) {
+            Some(first) => diagnostics.push(
+                Diagnostic::error(codes::DUPLICATE_NAME, message, span)
+                    .with_label(*first, "first defined here"),
+            ),
+            None => {
+                seen.insert((name, namespace), span);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::diagnostic::Severity;
+    use crate::diagnostics::render::line_col;
+    use crate::parser::parser::parse_source;
+
+    fn check(input: &str) -> Vec<Diagnostic> {
+        validate(&parse_source(input).unwrap())
+    }
+
+    // underlined returns the source text that each diagnostic points at.
+    fn underlined<'a>(input: &'a str, diagnostics: &[Diagnostic]) -> Vec<&'a str> {
+        diagnostics
+            .iter()
+            .map(|d| &input[d.span.start..d.span.end])
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_program() {
+        let input = include_str!("../../examples/basic_app.kp");
+        assert!(check(input).is_empty());
+    }
+
+    #[test]
+    fn test_missing_image() {
+        let input = "deploy app web {\n    replicas: 2;\n}";
+        let diagnostics = check(input);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, codes::MISSING_IMAGE);
+        assert_eq!(diagnostics[0].message, "deployment `web` has no image");
+        assert_eq!(diagnostics[0].span, Span::new(11, 14));
+    }
+
+    #[test]
+    fn test_duplicate_names() {
+        let input = "deploy app web { image: a; }\ndeploy app web { image: b; }\ndeploy app web { namespace: other; image: c; }\nservice web { }\nservice web { }";
+        let diagnostics = check(input);
+        let codes: Vec<&str> = diagnostics.iter().map(|d| d.code).collect();
+        assert_eq!(codes, vec![codes::DUPLICATE_NAME, codes::DUPLICATE_NAME]);
+        assert_eq!(
+            diagnostics[0].message,
+            "deployment `web` is defined more than once in namespace `default`"
+        );
+        assert_eq!(diagnostics[0].labels[0].span, Span::new(11, 14));
+        assert!(diagnostics[1].message.starts_with("service `web`"));
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        let input = "deploy app Web_1 { namespace: Prod; image: a; }\nservice -svc { }";
+        let messages: Vec<String> = check(input).into_iter().map(|d| d.message).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "deployment name `Web_1` is not a valid DNS-1123 label",
+                "namespace `Prod` is not a valid DNS-1123 label",
+                "service name `-svc` is not a valid DNS-1123 label",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_negative_replicas() {
+        let input = "deploy app web { replicas: -1; image: a; }";
+        let diagnostics = check(input);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, codes::OUT_OF_RANGE);
+        assert_eq!(
+            diagnostics[0].message,
+            "deployment `web` has a negative replica count (-1)"
+        );
+        assert_eq!(underlined(input, &diagnostics), vec!["replicas: -1"]);
+    }
+
+    #[test]
+    fn test_port_ranges_and_names() {
+        let input = "\
+deploy app web {
+    image: a;
+    ports { http: 0; metrics-http-port: 9090; grpc: 70000; }
+}
+service web {
+    ports { port: 80; targetPort: 65536; }
+}";
+        let diagnostics = check(input);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "port `http` 0 is outside the range 1-65535",
+                "port name `metrics-http-port` is not valid",
+                "port `grpc` 70000 is outside the range 1-65535",
+                "target port 65536 is outside the range 1-65535",
+            ]
+        );
+        assert_eq!(
+            underlined(input, &diagnostics),
+            vec![
+                "http: 0",
+                "metrics-http-port: 9090",
+                "grpc: 70000",
+                "targetPort: 65536"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeated_ports() {
+        let input = "\
+deploy app web {
+    image: a;
+    ports { http: 80; }
+    ports { http: 81; grpc: 9000; }
+}
+service web {
+    ports { port: 80; targetPort: 8080; }
+    ports { port: 80; targetPort: 9090; port: 443; }
+}";
+        let diagnostics = check(input);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "deployment `web` defines port `http` more than once",
+                "service `web` defines port `80` more than once",
+            ]
+        );
+        assert!(diagnostics.iter().all(|d| d.code == codes::DUPLICATE_PORT));
+        assert_eq!(
+            underlined(input, &diagnostics),
+            vec!["http: 81", "port: 80"]
+        );
+        assert_eq!(diagnostics[0].labels[0].message, "first defined here");
+        let first = diagnostics[0].labels[0].span;
+        assert_eq!(&input[first.start..first.end], "http: 80");
+        let first = diagnostics[1].labels[0].span;
+        assert_eq!(line_col(input, first.start), (7, 13));
+    }
+
+    #[test]
+    fn test_probes() {
+        let input = "\
+deploy app web {
+    image: a;
+    ports { http: 8080; }
+    probes {
+        liveness { http: /healthz; port: http; successThreshold: 2; }
+        readiness { grpc: grpc; period: 0s; }
+        startup { tcp: 0; }
+    }
+}";
+        let diagnostics = check(input);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "liveness probe `successThreshold` must be 1",
+                "readiness probe refers to unknown port `grpc`",
+                "readiness probe `period` must be at least 1",
+                "startup probe port 0 is outside the range 1-65535",
+            ]
+        );
+        assert_eq!(diagnostics[1].code, codes::UNKNOWN_PORT);
+        assert_eq!(line_col(input, diagnostics[1].span.start), (6, 9));
+
+        let valid = "deploy app web { image: a; ports { http: 80; } probes { readiness { http: /; port: http; successThreshold: 3; } startup { exec: [true]; } } }";
+        assert!(check(valid).is_empty());
+    }
+
+    #[test]
+    fn test_labels_and_annotations() {
+        let input = "\
+deploy app web {
+    image: a;
+    labels { tier: front-end; app.kubernetes.io/name: x; -bad: y; team: a/b; }
+    annotations { example.com/note: \"any value\"; Example.com/x: y; }
+}";
+        let diagnostics = check(input);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "label `app.kubernetes.io/name` is generated and cannot be set",
+                "label key `-bad` is not valid",
+                "value `a/b` of label `team` is not valid",
+                "annotation key `Example.com/x` is not valid",
+            ]
+        );
+        assert_eq!(
+            underlined(input, &diagnostics),
+            vec![
+                "app.kubernetes.io/name: x",
+                "-bad: y",
+                "team: a/b",
+                "Example.com/x: y"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_statefulsets() {
+        let input = "\
+service db { type: headless; }
+deploy statefulset db {
+    image: postgres;
+    replicas: -1;
+    probes { liveness { tcp: sql; } }
+}
+service db-public { app: db; }
+deploy statefulset cache { serviceName: Cache; }
+deploy statefulset db { image: postgres; }";
+        let diagnostics = check(input);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "statefulset `db` has a negative replica count (-1)",
+                "liveness probe refers to unknown port `sql`",
+                "statefulset `cache` has no image",
+                "service name `Cache` is not a valid DNS-1123 label",
+                "statefulset `cache` uses service `Cache`, which is not defined in namespace `default`",
+                "statefulset `db` is defined more than once in namespace `default`",
+            ]
+        );
+        assert_eq!(
+            diagnostics[1].help.as_deref(),
+            Some("declare it in the `ports` block of statefulset `db`")
+        );
+        assert_eq!(diagnostics[4].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[4].help.as_deref(),
+            Some("add a `service Cache { type: headless; }` block")
+        );
+    }
+
+    #[test]
+    fn test_jobs() {
+        let long_name = "r".repeat(53);
+        let input = format!(
+            "\
+job migrate {{
+    backoffLimit: -1;
+    parallelism: -2;
+    activeDeadlineSeconds: 0;
+}}
+cronjob {} {{ schedule: \"@daily\"; image: a; }}
+cronjob report {{ schedule: \"@daily\"; image: a; completions: -1; }}
+job migrate {{ image: a; }}",
+            long_name
+        );
+        let diagnostics = check(&input);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "job `migrate` has no image",
+                "job `migrate` `backoffLimit` must be at least 0",
+                "job `migrate` `parallelism` must be at least 0",
+                "job `migrate` `activeDeadlineSeconds` must be at least 1",
+                &format!("cronjob name `{}` is longer than 52 characters", long_name),
+                "cronjob `report` `completions` must be at least 0",
+                "job `migrate` is defined more than once in namespace `default`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_autoscale() {
+        let input = "\
+deploy app web {
+    image: a;
+    resources { requests { cpu: 100m; } }
+    autoscale { min: 2; max: 10; cpu: 70%; memory: 80%; }
+}
+deploy app api {
+    image: a;
+    autoscale { min: 0; max: 3; cpu: 0%; memory: 256Mi; }
+}
+deploy statefulset db {
+    image: a;
+    autoscale { min: 5; max: 3; }
+}
+service db { type: headless; }";
+        let diagnostics = check(input);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "deployment `web` autoscales on memory utilization but does not request memory",
+                "deployment `api` autoscale `min` must be at least 1",
+                "deployment `api` autoscale cpu utilization must be at least 1%",
+                "deployment `api` autoscales on cpu utilization but does not request cpu",
+                "statefulset `db` autoscale `min` (5) is greater than `max` (3)",
+            ]
+        );
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].code, codes::MISSING_REQUEST);
+        assert_eq!(diagnostics[1].code, codes::OUT_OF_RANGE);
+    }
+
+    #[test]
+    fn test_disruption_budgets() {
+        let input = "\
+deploy app web { image: a; replicas: 3; disruption { minAvailable: 2; } }
+deploy app api { image: a; replicas: 2; disruption { minAvailable: 2; } }
+deploy app worker { image: a; disruption { maxUnavailable: 0%; } }
+deploy app cache { image: a; disruption { minAvailable: 100%; } }
+deploy app queue { image: a; disruption { maxUnavailable: 150%; } }
+deploy statefulset db {
+    image: a;
+    autoscale { min: 2; max: 5; }
+    disruption { minAvailable: 2; }
+}
+service db { type: headless; }";
+        let diagnostics = check(input);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "deployment `api` disruption budget `minAvailable: 2` never allows an eviction with 2 replicas",
+                "deployment `worker` disruption budget `maxUnavailable: 0%` never allows an eviction with 1 replicas",
+                "deployment `cache` disruption budget `minAvailable: 100%` never allows an eviction with 1 replicas",
+                "deployment `queue` disruption `maxUnavailable` (150%) must be a count of at least 0 or a percentage up to 100%",
+                "statefulset `db` disruption budget `minAvailable: 2` never allows an eviction with 2 replicas",
+            ]
+        );
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].code, codes::BLOCKED_EVICTION);
+        assert_eq!(diagnostics[3].code, codes::OUT_OF_RANGE);
+    }
+
+    #[test]
+    fn test_network_references() {
+        let input = "\
+deploy app api {
+    image: a;
+    ports { http: 8080; }
+    network {
+        allow from app(frontend), app(ghost) on http, metrics;
+        allow to cidr(\"10.0.0.0/8\") on sql, 70000;
+        allow to app(cache) on redis;
+        allow to app(frontend), app(cache) on http;
+        deny all egress;
+    }
+}
+deploy app frontend { image: a; ports { http: 80; } }
+deploy app cache { image: a; namespace: other; ports { redis: 6379; } }";
+        let diagnostics = check(input);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "network rule of deployment `api` refers to app `ghost`, which is not defined in namespace `default`",
+                "deployment `api` has no port `metrics`",
+                "port `sql` cannot be resolved for a `cidr(...)` peer",
+                "network rule port 70000 is outside the range 1-65535",
+                "network rule of deployment `api` refers to app `cache`, which is not defined in namespace `default`",
+                "network rule of deployment `api` refers to app `cache`, which is not defined in namespace `default`",
+            ]
+        );
+        assert_eq!(diagnostics[0].code, codes::UNKNOWN_REFERENCE);
+        assert_eq!(line_col(input, diagnostics[0].span.start).0, 5);
+        assert_eq!(diagnostics[1].code, codes::UNKNOWN_PORT);
+        assert_eq!(
+            diagnostics[1].help.as_deref(),
+            Some("declare it in the `ports` block of deployment `api`")
+        );
+
+        let input = "\
+deploy app api { image: a; network { allow to app(db), app(cache) on sql; } }
+deploy app db { image: a; ports { sql: 5432; } }
+deploy app cache { image: a; }";
+        let messages: Vec<String> = check(input).into_iter().map(|d| d.message).collect();
+        assert_eq!(messages, vec!["app `cache` has no port `sql`"]);
+    }
+
+    #[test]
+    fn test_roles_and_bindings() {
+        let input = "\
+deploy app web { image: a; namespace: shop; serviceAccount: web-sa; }
+role reader {
+    namespace: shop;
+    rules {
+        pods: [get, read];
+        podz: [get];
+        nodes: [list];
+    }
+}
+role viewer { scope: cluster; rules { nodes: [list]; } }
+role viewer { scope: cluster; }
+role reader { rules { pods: [get]; } }
+bind reader to web-sa;
+bind viewer to Web_SA;
+bind writer to web-sa;";
+        let diagnostics = check(input);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "role `reader` grants unknown verb `read` on `pods`",
+                "role `reader` refers to unknown resource `podz`",
+                "role `reader` cannot grant cluster-scoped resource `nodes`",
+                "cluster role `viewer` is defined more than once",
+                "role `reader` is bound to service account `web-sa`, which no workload in namespace `default` runs as",
+                "service account name `Web_SA` is not a valid DNS-1123 label",
+                "cluster role `viewer` is bound to service account `Web_SA`, which no workload runs as",
+                "binding refers to role `writer`, which is not defined",
+            ]
+        );
+        assert_eq!(diagnostics[0].code, codes::UNKNOWN_RESOURCE);
+        assert_eq!(line_col(input, diagnostics[0].span.start).0, 5);
+        assert_eq!(
+            diagnostics[0].help.as_deref(),
+            Some("use one of get, list, watch, create, update, patch, delete, deletecollection")
+        );
+        assert_eq!(diagnostics[2].severity, Severity::Warning);
+        assert_eq!(diagnostics[4].severity, Severity::Warning);
+        assert_eq!(diagnostics[7].code, codes::UNKNOWN_REFERENCE);
+    }
+
+    #[test]
+    fn test_ingress_references() {
+        let input = "\
+deploy app web { image: a; ports { http: 8080; metrics: 9090; } }
+service web { ports { port: 80; targetPort: 8080; port: 443; targetPort: 8443; } }
+ingress api {
+    host: \"*.example.com\";
+    routes {
+        /: service(web, http);
+        /metrics: service(web, metrics);
+        /v2: service(web, 8080);
+        /admin: service(admin, 80);
+    }
+}
+ingress Bad { host: \"Example.com\"; }";
+        let diagnostics = check(input);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "service `web` has no port `metrics`",
+                "service `web` has no port `8080`",
+                "route `/admin` refers to service `admin`, which is not defined in namespace `default`",
+                "ingress name `Bad` is not a valid DNS-1123 label",
+                "ingress host `Example.com` is not a valid DNS name",
+            ]
+        );
+        assert_eq!(diagnostics[0].code, codes::UNKNOWN_PORT);
+        assert_eq!(line_col(input, diagnostics[0].span.start).0, 7);
+        assert_eq!(
+            diagnostics[0].help.as_deref(),
+            Some("name a container port of app `web` that the service forwards to")
+        );
+        assert_eq!(
+            diagnostics[1].help.as_deref(),
+            Some("service `web` exposes port 80, 443")
+        );
+        assert_eq!(diagnostics[2].code, codes::UNKNOWN_REFERENCE);
+        assert_eq!(diagnostics[2].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_service_targets_unknown_app() {
+        let input = "\
+deploy app web { image: a; }
+service web-public { app: web; }
+service api { app: api; }
+service db { }";
+        let diagnostics = check(input);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].code, codes::UNKNOWN_REFERENCE);
+        assert_eq!(
+            diagnostics[0].message,
+            "service `api` targets app `api`, which is not deployed in namespace `default`"
+        );
+    }
+
+    #[test]
+    fn test_name_formats_of_metadata() {
+        assert!(is_qualified_name("tier"));
+        assert!(is_qualified_name("example.com/owner"));
+        assert!(is_qualified_name("app.kubernetes.io/part-of"));
+        assert!(!is_qualified_name("example.com/"));
+        assert!(!is_qualified_name("/owner"));
+        assert!(!is_qualified_name("a/b/c"));
+        assert!(!is_qualified_name(&"x".repeat(64)));
+        assert!(is_label_value(""));
+        assert!(is_label_value("v1.2_rc-1"));
+        assert!(!is_label_value("-v1"));
+        assert!(!is_label_value("a b"));
+    }
+
+    #[test]
+    fn test_configs_and_secrets() {
+        let input = "\
+config app-config { data { level: debug; bad/key: x; } }
+secret Creds { }
+config app-config { }";
+        let messages: Vec<String> = check(input).into_iter().map(|d| d.message).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "config key `bad/key` is not valid",
+                "secret name `Creds` is not a valid DNS-1123 label",
+                "config `app-config` is defined more than once in namespace `default`",
+            ]
+        );
+        assert!(is_config_key("app.properties"));
+        assert!(is_config_key("1_X-y"));
+        assert!(!is_config_key(""));
+    }
+
+    #[test]
+    fn test_env_references() {
+        let input = "\
+config app-config { data { level: debug; } }
+secret db-creds { data { password: x; } }
+deploy app web {
+    image: a;
+    env {
+        LEVEL: config(app-config, level);
+        USER: secret(db-creds, username);
+    }
+    envFrom: secret(api-keys);
+}";
+        let diagnostics = check(input);
+        let messages: Vec<(Severity, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.severity, d.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (Severity::Error, "secret `db-creds` has no key `username`"),
+                (
+                    Severity::Warning,
+                    "deployment `web` refers to secret `api-keys`, which is not defined in namespace `default`"
+                ),
+            ]
+        );
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.code == codes::UNKNOWN_REFERENCE));
+    }
+
+    #[test]
+    fn test_env_keys() {
+        let input = "deploy app web { image: a; env { DB_URL: x; 1ST: y; app.mode: z; } }";
+        let diagnostics = check(input);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, codes::INVALID_NAME);
+        assert_eq!(
+            diagnostics[0].message,
+            "environment variable name `1ST` is not valid"
+        );
+        assert_eq!(underlined(input, &diagnostics), vec!["1ST: y"]);
+    }
+
+    #[test]
+    fn test_requests_exceed_limits() {
+        let input = "\
+deploy app web {
+    image: a;
+    resources {
+        limits { memory: 1Gi; cpu: 500m; }
+        requests { memory: 2048Mi; cpu: 0.25; }
+    }
+}";
+        let diagnostics = check(input);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, codes::REQUEST_EXCEEDS_LIMIT);
+        assert_eq!(
+            diagnostics[0].message,
+            "memory request `2Gi` exceeds the limit `1Gi`"
+        );
+        assert_eq!(underlined(input, &diagnostics), vec!["memory: 2048Mi"]);
+    }
+
+    #[test]
+    fn test_name_formats() {
+        assert!(is_dns_label("web-1"));
+        assert!(!is_dns_label(""));
+        assert!(!is_dns_label("web-"));
+        assert!(!is_dns_label(&"a".repeat(64)));
+        assert!(is_dns_subdomain("api.example.com"));
+        assert!(is_dns_subdomain("localhost"));
+        assert!(!is_dns_subdomain("api..example.com"));
+        assert!(!is_dns_subdomain("Api.example.com"));
+        assert!(!is_dns_subdomain(&vec!["a".repeat(63); 4].join(".")));
+        assert!(is_port_name("http"));
+        assert!(!is_port_name("8080"));
+        assert!(!is_port_name("a--b"));
+        assert!(is_env_key("_HOME"));
+        assert!(!is_env_key(""));
+    }
+}